@@ -0,0 +1,69 @@
+//! Benchmarks of elevation queries, per point and in batches.
+//!
+//! Run with `cargo bench`, or `cargo bench --features rayon`
+//! to evaluate the batches in parallel.
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use dted2::dted::Interpolation;
+use dted2::primitives::AxisElement;
+use dted2::DTEDData;
+
+/// Number of points per query
+const POINTS: usize = 100_000;
+
+/// Pseudo-random points within the data (a linear congruential
+/// generator, such that every run queries the same points)
+fn points(data: &DTEDData) -> Vec<AxisElement<f64>> {
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    let extent = data.max - data.min;
+    (0..POINTS)
+        .map(|_| {
+            let lat = data.min.lat + next() * extent.lat;
+            AxisElement::new(lat, data.min.lon + next() * extent.lon)
+        })
+        .collect()
+}
+
+fn elevation(c: &mut Criterion) {
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    let points = points(&data);
+    let lats: Vec<f64> = points.iter().map(|p| p.lat).collect();
+    let lons: Vec<f64> = points.iter().map(|p| p.lon).collect();
+    let mut out = vec![None; POINTS];
+
+    let mut group = c.benchmark_group("elevation");
+    group.throughput(Throughput::Elements(POINTS as u64));
+    for interpolation in [Interpolation::Bilinear, Interpolation::Bicubic] {
+        let name = format!("{:?}", interpolation);
+        group.bench_function(BenchmarkId::new("per_point", &name), |b| {
+            b.iter(|| {
+                for (out, p) in out.iter_mut().zip(points.iter()) {
+                    *out = data.get_elevation_with(p.lat, p.lon, interpolation);
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("batch", &name), |b| {
+            b.iter(|| data.get_elevations(&lats, &lons, interpolation, &mut out).unwrap())
+        });
+        group.bench_function(BenchmarkId::new("batch_at", &name), |b| {
+            b.iter(|| data.get_elevations_at(&points, interpolation, &mut out).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, elevation);
+criterion_main!(benches);
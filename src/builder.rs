@@ -0,0 +1,306 @@
+//! Construction of DTED data from an elevation grid, rather than a file.
+//!
+//! The main entry point is [`DTEDBuilder`].
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{
+    DTEDGrid, DTEDMetadata, DTEDRecordACC, DTEDRecordDSI, RawDTEDHeader, RawDTEDRecord,
+    SecurityCode, DT2_RECORD_OVERHEAD_LENGTH,
+};
+use crate::primitives::{Angle, AxisElement};
+use crate::writers;
+use crate::DTEDData;
+use crate::Error as DTEDError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Order of the posts of an elevation grid, see [DTEDBuilder]
+///
+/// Latitude indices increase northwards from the origin, and longitude
+/// indices increase eastwards, as in [DTEDData::get_indices]. Rasters
+/// stored north-up should have their rows reversed
+pub enum GridOrder {
+    /// Rows of latitude points, i.e. indexed as `[lat_index][lon_index]`
+    RowMajor,
+    /// Columns of longitude lines, i.e. indexed as `[lon_index][lat_index]`,
+    /// the order of the data records of a DTED file
+    ColumnMajor,
+}
+
+#[derive(Debug, Clone)]
+/// Elevations given to a [DTEDBuilder], validated by [DTEDBuilder::build]
+enum Elevations {
+    Flat(Vec<i16>),
+    Nested(Vec<Vec<i16>>),
+}
+
+#[derive(Debug, Clone)]
+/// Builder of [DTEDData] from an elevation grid
+///
+/// The header records are generated from the origin, interval and count
+/// (see [DTEDRecordDSI::from_metadata] when no DSI record is given), and the
+/// checksum of each data record is computed, so the built data can be used
+/// with the query APIs (e.g. [DTEDData::get_elevation]) and [DTEDData::write]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::builder::{ DTEDBuilder, GridOrder };
+/// use dted2::dted::DTEDLevel;
+///
+/// // level 0 cell at 30S 178W, rising to the east
+/// let grid = DTEDLevel::Level0.grid(-29.5, -177.5);
+/// let elevations: Vec<Vec<i16>> = (0..121).map(|_| (0..121).collect()).collect();
+/// let data = DTEDBuilder::from_grid(&grid)
+///     .grid(&elevations, GridOrder::RowMajor)
+///     .accuracy(30)
+///     .build()
+///     .unwrap();
+/// assert_eq!(data.metadata.level, Some(DTEDLevel::Level0));
+/// assert_eq!(data.get_elevation(-29.5, -178.0), Some(0.0));
+/// assert_eq!(data.get_elevation(-29.5, -177.5), Some(60.0));
+/// assert_eq!(data.get_vertical_accuracy(-29.5, -177.5), Some(30));
+/// ```
+pub struct DTEDBuilder {
+    origin: AxisElement<Angle>,
+    interval_secs: AxisElement<f32>,
+    count: AxisElement<u16>,
+    elevations: Elevations,
+    order: GridOrder,
+    accuracy: Option<u16>,
+    security_code: Option<SecurityCode>,
+    reference: Option<String>,
+    dsi: Option<DTEDRecordDSI>,
+    acc: Option<DTEDRecordACC>,
+}
+impl DTEDBuilder {
+    /// Create a [DTEDBuilder] without any elevations
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - latitude and longitude of the lower left corner of the grid
+    /// * `interval_secs` - interval in arc seconds, stored to a tenth of a second
+    /// * `count` - number of latitude points and longitude lines
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDBuilder]
+    pub fn new(
+        origin: AxisElement<Angle>,
+        interval_secs: AxisElement<f32>,
+        count: AxisElement<u16>,
+    ) -> DTEDBuilder {
+        DTEDBuilder {
+            origin,
+            interval_secs,
+            count,
+            elevations: Elevations::Flat(Vec::new()),
+            order: GridOrder::ColumnMajor,
+            accuracy: None,
+            security_code: None,
+            reference: None,
+            dsi: None,
+            acc: None,
+        }
+    }
+
+    /// Create a [DTEDBuilder] for a 1 degree cell, see [crate::dted::DTEDLevel::grid]
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - [DTEDGrid] of the cell
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDBuilder]
+    pub fn from_grid(grid: &DTEDGrid) -> DTEDBuilder {
+        let angle = |deg: i16| Angle::new(deg.unsigned_abs(), 0, 0.0, deg < 0);
+        DTEDBuilder::new(
+            AxisElement::new(angle(grid.origin.lat), angle(grid.origin.lon)),
+            grid.interval_secs,
+            grid.count,
+        )
+    }
+
+    /// Set the elevations (in meters) from a flat vector
+    ///
+    /// # Arguments
+    ///
+    /// * `elevations` - `count.lat * count.lon` posts
+    /// * `order` - [GridOrder] of the posts
+    pub fn elevations(mut self, elevations: Vec<i16>, order: GridOrder) -> DTEDBuilder {
+        self.elevations = Elevations::Flat(elevations);
+        self.order = order;
+        self
+    }
+
+    /// Set the elevations (in meters) from a 2D slice
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - rows or columns of posts
+    /// * `order` - [GridOrder] of the posts
+    pub fn grid<R: AsRef<[i16]>>(mut self, grid: &[R], order: GridOrder) -> DTEDBuilder {
+        self.elevations = Elevations::Nested(grid.iter().map(|r| r.as_ref().to_vec()).collect());
+        self.order = order;
+        self
+    }
+
+    /// Set the absolute vertical accuracy (in meters)
+    pub fn accuracy(mut self, accuracy: u16) -> DTEDBuilder {
+        self.accuracy = Some(accuracy);
+        self
+    }
+
+    /// Set the security classification code
+    pub fn security_code(mut self, security_code: SecurityCode) -> DTEDBuilder {
+        self.security_code = Some(security_code);
+        self
+    }
+
+    /// Set the unique reference number
+    pub fn reference<S: Into<String>>(mut self, reference: S) -> DTEDBuilder {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    /// Set the [DTEDRecordDSI]. The origin, corners, interval and count
+    /// are always written from the grid, see [crate::writers::dted_dsi_bytes]
+    pub fn dsi(mut self, dsi: DTEDRecordDSI) -> DTEDBuilder {
+        self.dsi = Some(dsi);
+        self
+    }
+
+    /// Set the [DTEDRecordACC]
+    pub fn acc(mut self, acc: DTEDRecordACC) -> DTEDBuilder {
+        self.acc = Some(acc);
+        self
+    }
+
+    /// Build the [DTEDData]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData], or [DTEDError::DimensionMismatch] if the
+    ///   elevations do not match the count (or the count is less than 2)
+    pub fn build(self) -> Result<DTEDData, DTEDError> {
+        let columns = self.columns()?;
+        // --------------------------------------------------
+        // metadata, as if read from the generated UHL
+        // --------------------------------------------------
+        let header = RawDTEDHeader {
+            origin: self.origin,
+            interval_secs_x_10: AxisElement::new(
+                (self.interval_secs.lat * 10.0).round() as u16,
+                (self.interval_secs.lon * 10.0).round() as u16,
+            ),
+            accuracy: self.accuracy,
+            security_code: self.security_code,
+            reference: self.reference,
+            count: self.count,
+            multiple_accuracy: self.acc.as_ref().is_some_and(|acc| !acc.sub_regions.is_empty()),
+        };
+        let mut metadata = DTEDMetadata::from_header(&header, None);
+        metadata.dsi = Some(match self.dsi {
+            Some(dsi) => dsi,
+            None => DTEDRecordDSI::from_metadata(&metadata),
+        });
+        metadata.acc = self.acc;
+        metadata.validate();
+        // --------------------------------------------------
+        // data records, with their checksums
+        // --------------------------------------------------
+        let mut record = Vec::with_capacity(DT2_RECORD_OVERHEAD_LENGTH + 2 * self.count.lat as usize);
+        let data = columns
+            .into_iter()
+            .enumerate()
+            .map(|(column, elevations)| {
+                writers::dted_record_bytes(column, &elevations, &mut record);
+                let checksum = &record[record.len() - 4..];
+                RawDTEDRecord {
+                    blk_count: column as u32,
+                    lon_count: column as u16,
+                    lat_count: 0,
+                    elevations,
+                    checksum: u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]),
+                }
+            })
+            .collect();
+        let min = metadata.origin;
+        let max = min + ((self.count - 1) * metadata.interval);
+        Ok(DTEDData {
+            metadata,
+            min,
+            max,
+            data,
+            invalid_checksums: Vec::new(),
+        })
+    }
+
+    /// Validate the dimensions of the elevations, and
+    /// reorder them into columns (longitude lines)
+    fn columns(&self) -> Result<Vec<Vec<i16>>, DTEDError> {
+        let lat_count = self.count.lat as usize;
+        let lon_count = self.count.lon as usize;
+        // --------------------------------------------------
+        // interpolation requires at least 2 posts along each axis
+        // --------------------------------------------------
+        if lat_count < 2 {
+            return Err(DTEDError::DimensionMismatch {
+                axis: "latitude points",
+                expected: 2,
+                found: lat_count,
+            });
+        }
+        if lon_count < 2 {
+            return Err(DTEDError::DimensionMismatch {
+                axis: "longitude lines",
+                expected: 2,
+                found: lon_count,
+            });
+        }
+        let (outer, inner) = match self.order {
+            GridOrder::RowMajor => (("latitude points", lat_count), ("longitude lines", lon_count)),
+            GridOrder::ColumnMajor => (("longitude lines", lon_count), ("latitude points", lat_count)),
+        };
+        // --------------------------------------------------
+        // verify the dimensions, as rows or columns
+        // --------------------------------------------------
+        let grid: Vec<&[i16]> = match &self.elevations {
+            Elevations::Flat(posts) => {
+                if posts.len() != lat_count * lon_count {
+                    return Err(DTEDError::DimensionMismatch {
+                        axis: "posts",
+                        expected: lat_count * lon_count,
+                        found: posts.len(),
+                    });
+                }
+                posts.chunks(inner.1).collect()
+            }
+            Elevations::Nested(grid) => {
+                if grid.len() != outer.1 {
+                    return Err(DTEDError::DimensionMismatch {
+                        axis: outer.0,
+                        expected: outer.1,
+                        found: grid.len(),
+                    });
+                }
+                if let Some(line) = grid.iter().find(|line| line.len() != inner.1) {
+                    return Err(DTEDError::DimensionMismatch {
+                        axis: inner.0,
+                        expected: inner.1,
+                        found: line.len(),
+                    });
+                }
+                grid.iter().map(Vec::as_slice).collect()
+            }
+        };
+        Ok(match self.order {
+            GridOrder::ColumnMajor => grid.into_iter().map(<[i16]>::to_vec).collect(),
+            GridOrder::RowMajor => (0..lon_count)
+                .map(|lon| grid.iter().map(|row| row[lon]).collect())
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,274 @@
+//! Bounded, thread-safe cache of DTED tiles, loaded on demand.
+//!
+//! The main entry point is [`DTEDCache`].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{DTEDReadOptions, RawDTEDRecord};
+use crate::DTEDData;
+use crate::Error as DTEDError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Budget of a [DTEDCache], beyond which the least recently used tiles are evicted
+///
+/// The most recently used tile is always kept, even if it alone exceeds the budget
+pub enum CacheBudget {
+    /// Maximum number of tiles
+    Tiles(usize),
+    /// Maximum (approximate) memory used by the tiles in bytes, see [CacheStats::bytes]
+    Bytes(usize),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Statistics of a [DTEDCache]
+///
+/// # Fields
+///
+/// * `hits` - number of requests served from the cache
+/// * `misses` - number of requests which read the file
+/// * `evictions` - number of tiles evicted to stay within the [CacheBudget]
+/// * `tiles` - number of tiles currently cached
+/// * `bytes` - approximate memory used by the cached tiles in bytes
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub tiles: usize,
+    pub bytes: usize,
+}
+
+/// Cached tile
+struct CacheEntry {
+    data: Arc<DTEDData>,
+    bytes: usize,
+    last_used: u64,
+}
+
+#[derive(Default)]
+/// State of a [DTEDCache], guarded by its mutex
+struct CacheState {
+    entries: HashMap<PathBuf, CacheEntry>,
+    /// Paths of the cached tiles, from the least to the most recently used
+    order: BTreeMap<u64, PathBuf>,
+    /// Paths currently being read by a thread
+    loading: HashSet<PathBuf>,
+    tick: u64,
+    stats: CacheStats,
+}
+impl CacheState {
+    /// Get a cached tile, marking it as the most recently used
+    fn touch(&mut self, path: &Path) -> Option<Arc<DTEDData>> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(path)?;
+        let path = self.order.remove(&entry.last_used)?;
+        entry.last_used = self.tick;
+        self.order.insert(self.tick, path);
+        Some(entry.data.clone())
+    }
+
+    /// Cache a tile as the most recently used, evicting
+    /// the least recently used tiles beyond the budget
+    fn insert(&mut self, path: PathBuf, data: Arc<DTEDData>, budget: CacheBudget) {
+        self.tick += 1;
+        let bytes = tile_bytes(&data);
+        if let Some(old) = self.entries.remove(&path) {
+            self.order.remove(&old.last_used);
+            self.stats.bytes -= old.bytes;
+        }
+        self.order.insert(self.tick, path.clone());
+        self.entries.insert(path, CacheEntry { data, bytes, last_used: self.tick });
+        self.stats.bytes += bytes;
+        while self.entries.len() > 1 && self.over(budget) {
+            let (_, path) = match self.order.pop_first() {
+                Some(oldest) => oldest,
+                None => break,
+            };
+            if let Some(entry) = self.entries.remove(&path) {
+                self.stats.bytes -= entry.bytes;
+                self.stats.evictions += 1;
+            }
+        }
+        self.stats.tiles = self.entries.len();
+    }
+
+    /// Whether or not the cached tiles exceed the budget
+    fn over(&self, budget: CacheBudget) -> bool {
+        match budget {
+            CacheBudget::Tiles(tiles) => self.entries.len() > tiles,
+            CacheBudget::Bytes(bytes) => self.stats.bytes > bytes,
+        }
+    }
+}
+
+/// Marks a path as being read, until dropped (even if reading panics)
+struct Loading<'a> {
+    cache: &'a DTEDCache,
+    path: &'a Path,
+}
+impl Drop for Loading<'_> {
+    fn drop(&mut self) {
+        self.cache.state().loading.remove(self.path);
+        self.cache.loaded.notify_all();
+    }
+}
+
+/// Bounded cache of DTED tiles, read on demand with [DTEDData::read_with_options]
+///
+/// Tiles are shared as [Arc] handles, and evicted in least recently used
+/// order once the [CacheBudget] is exceeded. Evicted tiles remain valid
+/// for as long as a handle is held. The cache can be shared between threads
+/// (e.g. within an [Arc]), and a file requested by several threads at once
+/// is only read by one of them, while the others wait for it.
+///
+/// Tiles are keyed by their path as given, which is not canonicalized
+///
+/// # Examples
+///
+/// ```
+/// use dted2::cache::{ CacheBudget, DTEDCache };
+/// use std::sync::Arc;
+///
+/// let cache = DTEDCache::new(CacheBudget::Tiles(16));
+/// let data = cache.get("tests/test_data_negative.dt2").unwrap();
+/// assert!(data.get_elevation(-29.5, -177.5).is_some());
+/// let again = cache.get("tests/test_data_negative.dt2").unwrap();
+/// assert!(Arc::ptr_eq(&data, &again));
+/// let stats = cache.stats();
+/// assert_eq!((stats.hits, stats.misses, stats.tiles), (1, 1, 1));
+/// ```
+pub struct DTEDCache {
+    budget: CacheBudget,
+    options: DTEDReadOptions,
+    state: Mutex<CacheState>,
+    loaded: Condvar,
+}
+impl DTEDCache {
+    /// Create an empty [DTEDCache], which reads tiles with the default [DTEDReadOptions]
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - [CacheBudget]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDCache]
+    pub fn new(budget: CacheBudget) -> DTEDCache {
+        DTEDCache::with_options(budget, DTEDReadOptions::default())
+    }
+
+    /// Create an empty [DTEDCache], which reads tiles with [DTEDReadOptions]
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - [CacheBudget]
+    /// * `options` - [DTEDReadOptions] used to read every tile
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDCache]
+    pub fn with_options(budget: CacheBudget, options: DTEDReadOptions) -> DTEDCache {
+        DTEDCache {
+            budget,
+            options,
+            state: Mutex::new(CacheState::default()),
+            loaded: Condvar::new(),
+        }
+    }
+
+    /// Get a tile, reading the file if it is not cached
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * Shared [DTEDData], or an error if the file cannot be read
+    ///   (which is not cached, so the next request reads it again)
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Result<Arc<DTEDData>, DTEDError> {
+        let path = path.as_ref();
+        // --------------------------------------------------
+        // cached, or wait for another thread reading the file
+        // --------------------------------------------------
+        let mut state = self.state();
+        loop {
+            if let Some(data) = state.touch(path) {
+                state.stats.hits += 1;
+                return Ok(data);
+            }
+            if !state.loading.contains(path) {
+                break;
+            }
+            state = self.loaded.wait(state).unwrap_or_else(PoisonError::into_inner);
+        }
+        state.stats.misses += 1;
+        state.loading.insert(path.to_path_buf());
+        drop(state);
+        // --------------------------------------------------
+        // read the file without holding the lock
+        // --------------------------------------------------
+        let _loading = Loading { cache: self, path };
+        let data = Arc::new(DTEDData::read_with_options(path, &self.options)?);
+        self.state().insert(path.to_path_buf(), data.clone(), self.budget);
+        Ok(data)
+    }
+
+    /// Whether or not a tile is cached, without marking it as used
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.state().entries.contains_key(path.as_ref())
+    }
+
+    /// Remove a tile from the cache
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * The removed tile, if it was cached
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> Option<Arc<DTEDData>> {
+        let mut state = self.state();
+        let entry = state.entries.remove(path.as_ref())?;
+        state.order.remove(&entry.last_used);
+        state.stats.bytes -= entry.bytes;
+        state.stats.tiles = state.entries.len();
+        Some(entry.data)
+    }
+
+    /// Remove every tile from the cache, keeping the statistics
+    pub fn clear(&self) {
+        let mut state = self.state();
+        state.entries.clear();
+        state.order.clear();
+        state.stats.bytes = 0;
+        state.stats.tiles = 0;
+    }
+
+    /// Get the [CacheStats]
+    pub fn stats(&self) -> CacheStats {
+        self.state().stats
+    }
+
+    /// Lock the state. A panic while holding the lock cannot leave the
+    /// state inconsistent, so a poisoned lock is still used
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Approximate memory used by a tile in bytes
+fn tile_bytes(data: &DTEDData) -> usize {
+    let records = data.data.len() * std::mem::size_of::<RawDTEDRecord>();
+    let elevations: usize = data.data.iter().map(|r| 2 * r.elevations.capacity()).sum();
+    let header = data.metadata.header_bytes.as_ref().map_or(0, Vec::len);
+    std::mem::size_of::<DTEDData>() + records + elevations + header
+}
@@ -5,8 +5,12 @@
 // --------------------------------------------------
 // external
 // --------------------------------------------------
-use std::io::Read;
+use nom::sequence::tuple;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::Path;
 use thisenum::Const;
+use thiserror::Error as ThisError;
 
 // --------------------------------------------------
 // local
@@ -14,6 +18,7 @@ use thisenum::Const;
 use crate::parsers;
 use crate::primitives::{self, Angle, AxisElement};
 use crate::Error as DTEDError;
+use crate::Section;
 
 // --------------------------------------------------
 // constants
@@ -22,8 +27,29 @@ use crate::Error as DTEDError;
 pub const DT2_UHL_LENGTH: u64 = 80;
 /// Data Set Identification (DSI) Record Length
 pub const DT2_DSI_RECORD_LENGTH: usize = 648;
+/// Offset of the number of latitude lines within the DSI record
+const DT2_DSI_LAT_COUNT_OFFSET: usize = 281;
 /// Accuracy Description (ACC) Record Length
 pub const DT2_ACC_RECORD_LENGTH: usize = 2700;
+/// Total length of the UHL, DSI, and ACC records preceding the data records
+pub const DT2_HEADER_LENGTH: usize =
+    DT2_UHL_LENGTH as usize + DT2_DSI_RECORD_LENGTH + DT2_ACC_RECORD_LENGTH;
+/// Data Record overhead length (sentinel, block count,
+/// longitude count, latitude count, and checksum)
+pub const DT2_RECORD_OVERHEAD_LENGTH: usize = 12;
+/// Minimum number of points evaluated by each task of a parallel batch query
+#[cfg(feature = "rayon")]
+const BATCH_MIN_LEN: usize = 1024;
+/// Elevation marking a void (no data) post, `0xFFFF` in signed magnitude
+pub const DT2_VOID: i16 = -32767;
+/// Maximum number of accuracy sub-regions within the ACC record
+pub const DT2_ACC_MAX_SUB_REGIONS: usize = 9;
+/// Length of a single accuracy sub-region within the ACC record
+pub const DT2_ACC_SUB_REGION_LENGTH: usize = 284;
+/// Maximum number of coordinates outlining an accuracy sub-region
+pub const DT2_ACC_MAX_OUTLINE_COORDS: usize = 14;
+/// Length of a single coordinate pair outlining an accuracy sub-region
+pub const DT2_ACC_COORD_LENGTH: usize = 19;
 
 #[derive(Const)]
 #[armtype(&[u8])]
@@ -71,6 +97,328 @@ pub enum RecognitionSentinel {
     NA,
 }
 
+#[derive(Const, Debug, Clone, Copy, PartialEq, Eq)]
+#[armtype(u8)]
+/// DTED Security Classification Code
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::SecurityCode;
+///
+/// assert_eq!(SecurityCode::Unclassified.value(), &b'U');
+/// assert_eq!(SecurityCode::try_from(b'S'), Ok(SecurityCode::Secret));
+/// assert!(SecurityCode::try_from(b'X').is_err());
+/// ```
+pub enum SecurityCode {
+    /// Secret
+    #[value = b'S']
+    Secret,
+    /// Confidential
+    #[value = b'C']
+    Confidential,
+    /// Unclassified
+    #[value = b'U']
+    Unclassified,
+    /// Restricted
+    #[value = b'R']
+    Restricted,
+}
+
+#[derive(Const, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[armtype(u8)]
+/// DTED product level, which determines the spacing of the posts
+///
+/// Latitude intervals are constant for each level, while longitude
+/// intervals widen in latitude zones further from the equator
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DTEDLevel;
+///
+/// assert_eq!(DTEDLevel::Level2.value(), &2);
+/// assert_eq!(DTEDLevel::try_from(1), Ok(DTEDLevel::Level1));
+/// assert_eq!(DTEDLevel::from_spacing(30.0, 121), Some(DTEDLevel::Level0));
+/// assert_eq!(DTEDLevel::from_spacing(30.0, 1201), None);
+/// assert_eq!(DTEDLevel::from_path("w178/s30.dt2"), Some(DTEDLevel::Level2));
+/// assert!(DTEDLevel::Level0 < DTEDLevel::Level2);
+/// ```
+pub enum DTEDLevel {
+    /// 30 arc second latitude interval
+    #[value = 0]
+    Level0,
+    /// 3 arc second latitude interval
+    #[value = 1]
+    Level1,
+    /// 1 arc second latitude interval
+    #[value = 2]
+    Level2,
+}
+impl DTEDLevel {
+    /// Latitude interval in arc seconds
+    pub fn lat_interval_secs(&self) -> f32 {
+        match self {
+            DTEDLevel::Level0 => 30.0,
+            DTEDLevel::Level1 => 3.0,
+            DTEDLevel::Level2 => 1.0,
+        }
+    }
+
+    /// Number of latitude points per longitude line of a 1 degree cell
+    pub fn lat_count(&self) -> u16 {
+        (primitives::SEC2DEG as f32 / self.lat_interval_secs()) as u16 + 1
+    }
+
+    /// File extension of the level (e.g. `dt2`)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DTEDLevel::Level0 => "dt0",
+            DTEDLevel::Level1 => "dt1",
+            DTEDLevel::Level2 => "dt2",
+        }
+    }
+
+    /// Derive the level from the latitude spacing of the posts
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_interval_secs` - latitude interval in arc seconds
+    /// * `lat_count` - number of latitude points per longitude line
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDLevel], or None if the spacing does not match any level
+    pub fn from_spacing(lat_interval_secs: f32, lat_count: u16) -> Option<DTEDLevel> {
+        [DTEDLevel::Level0, DTEDLevel::Level1, DTEDLevel::Level2]
+            .into_iter()
+            .find(|level| {
+                level.lat_interval_secs() == lat_interval_secs && level.lat_count() == lat_count
+            })
+    }
+
+    /// Get the level from the extension of a path (`dt0`, `dt1` or `dt2`, case insensitive)
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path to a DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDLevel], or None if the extension is not a DTED extension
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<DTEDLevel> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "dt0" => Some(DTEDLevel::Level0),
+            "dt1" => Some(DTEDLevel::Level1),
+            "dt2" => Some(DTEDLevel::Level2),
+            _ => None,
+        }
+    }
+
+    /// Get the level from the product level designator of
+    /// the [DTEDRecordDSI] (e.g. `DTED2`)
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - product level designator, see [DTEDRecordDSI::version]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDLevel], or None if the designator does not name a level
+    pub fn from_designator(version: &str) -> Option<DTEDLevel> {
+        let digit = version.trim().strip_prefix("DTED")?;
+        DTEDLevel::try_from(digit.parse::<u8>().ok()?).ok()
+    }
+
+    /// Longitude interval in arc seconds within a [LatitudeZone]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{ DTEDLevel, LatitudeZone };
+    ///
+    /// assert_eq!(DTEDLevel::Level2.lon_interval_secs(LatitudeZone::I), 1.0);
+    /// assert_eq!(DTEDLevel::Level1.lon_interval_secs(LatitudeZone::IV), 12.0);
+    /// ```
+    pub fn lon_interval_secs(&self, zone: LatitudeZone) -> f32 {
+        self.lat_interval_secs() * zone.lon_factor() as f32
+    }
+
+    /// Number of longitude lines of a 1 degree cell within a [LatitudeZone]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{ DTEDLevel, LatitudeZone };
+    ///
+    /// let counts: Vec<u16> = LatitudeZone::ALL
+    ///     .iter()
+    ///     .map(|&zone| DTEDLevel::Level2.lon_count(zone))
+    ///     .collect();
+    /// assert_eq!(counts, vec![3601, 1801, 1201, 901, 601]);
+    /// ```
+    pub fn lon_count(&self, zone: LatitudeZone) -> u16 {
+        (primitives::SEC2DEG as f32 / self.lon_interval_secs(zone)) as u16 + 1
+    }
+
+    /// Expected grid of the 1 degree cell containing a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDGrid] of the cell
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{ DTEDLevel, LatitudeZone };
+    /// use dted2::primitives::AxisElement;
+    ///
+    /// let grid = DTEDLevel::Level2.grid(-71.5, 12.25);
+    /// assert_eq!(grid.origin, AxisElement::new(-72, 12));
+    /// assert_eq!(grid.zone, LatitudeZone::III);
+    /// assert_eq!(grid.interval_secs, AxisElement::new(1.0, 3.0));
+    /// assert_eq!(grid.count, AxisElement::new(3601, 1201));
+    /// ```
+    pub fn grid<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> DTEDGrid {
+        let lat: f64 = lat.into();
+        let origin = AxisElement::new(lat.floor() as i16, lon.into().floor() as i16);
+        let zone = LatitudeZone::from_lat(lat);
+        DTEDGrid {
+            origin,
+            zone,
+            interval_secs: AxisElement::new(self.lat_interval_secs(), self.lon_interval_secs(zone)),
+            count: AxisElement::new(self.lat_count(), self.lon_count(zone)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// DTED latitude zone, within which the longitude interval is widened
+/// by a constant factor, such that the posts remain roughly square
+///
+/// A 1 degree cell belongs to the zone of its edge nearest the equator
+///
+/// | Zone | Latitude  | Longitude factor |
+/// |------|-----------|------------------|
+/// | I    | 0 - 50°   | 1                |
+/// | II   | 50 - 70°  | 2                |
+/// | III  | 70 - 75°  | 3                |
+/// | IV   | 75 - 80°  | 4                |
+/// | V    | 80 - 90°  | 6                |
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::LatitudeZone;
+///
+/// assert_eq!(LatitudeZone::from_lat(49.5), LatitudeZone::I);
+/// assert_eq!(LatitudeZone::from_lat(50.0), LatitudeZone::II);
+/// assert_eq!(LatitudeZone::from_lat(-50.5), LatitudeZone::II);
+/// assert_eq!(LatitudeZone::from_lat(-49.5), LatitudeZone::I);
+/// assert_eq!(LatitudeZone::from_lat(89.9), LatitudeZone::V);
+/// ```
+pub enum LatitudeZone {
+    I,
+    II,
+    III,
+    IV,
+    V,
+}
+impl LatitudeZone {
+    /// Every zone, from the equator to the poles
+    pub const ALL: [LatitudeZone; 5] = [
+        LatitudeZone::I,
+        LatitudeZone::II,
+        LatitudeZone::III,
+        LatitudeZone::IV,
+        LatitudeZone::V,
+    ];
+
+    /// Get the zone of the 1 degree cell containing a latitude
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    ///
+    /// # Returns
+    ///
+    /// * [LatitudeZone] of the cell
+    pub fn from_lat<T: Into<f64>>(lat: T) -> LatitudeZone {
+        // edge of the cell nearest the equator
+        let south = lat.into().floor();
+        let edge = if south >= 0.0 { south } else { -south - 1.0 };
+        LatitudeZone::ALL
+            .iter()
+            .rev()
+            .copied()
+            .find(|zone| edge >= zone.min_lat() as f64)
+            .unwrap_or(LatitudeZone::I)
+    }
+
+    /// Absolute latitude (in degrees) at which the zone starts
+    pub fn min_lat(&self) -> u8 {
+        match self {
+            LatitudeZone::I => 0,
+            LatitudeZone::II => 50,
+            LatitudeZone::III => 70,
+            LatitudeZone::IV => 75,
+            LatitudeZone::V => 80,
+        }
+    }
+
+    /// Factor of the longitude interval relative to the latitude interval
+    pub fn lon_factor(&self) -> u8 {
+        match self {
+            LatitudeZone::I => 1,
+            LatitudeZone::II => 2,
+            LatitudeZone::III => 3,
+            LatitudeZone::IV => 4,
+            LatitudeZone::V => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Expected grid of a 1 degree DTED cell, see [DTEDLevel::grid]
+///
+/// # Fields
+///
+/// * `origin` - latitude and longitude (in integer degrees) of the lower left corner
+/// * `zone` - [LatitudeZone] of the cell
+/// * `interval_secs` - interval in arc seconds
+/// * `count` - number of latitude points and longitude lines
+pub struct DTEDGrid {
+    pub origin: AxisElement<i16>,
+    pub zone: LatitudeZone,
+    pub interval_secs: AxisElement<f32>,
+    pub count: AxisElement<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ThisError)]
+/// Disagreement between the sources of the [DTEDLevel] of a DTED file,
+/// see [DTEDMetadata::level_warnings]
+pub enum LevelWarning {
+    /// The UHL intervals and counts do not match any level
+    #[error("UHL intervals and counts do not match any DTED level")]
+    Unknown,
+    /// The level designator of the DSI record disagrees with the UHL
+    #[error("DSI record designates {dsi:?}, but the UHL spacing is {uhl:?}")]
+    DSIMismatch { uhl: DTEDLevel, dsi: DTEDLevel },
+    /// The file extension disagrees with the UHL
+    #[error("File extension designates {extension:?}, but the UHL spacing is {uhl:?}")]
+    ExtensionMismatch { uhl: DTEDLevel, extension: DTEDLevel },
+    /// The longitude interval or count of the UHL does
+    /// not match the [LatitudeZone] of the cell
+    #[error("Longitude spacing does not match {level:?} in latitude zone {zone:?}")]
+    LonSpacingMismatch { level: DTEDLevel, zone: LatitudeZone },
+}
+
 #[derive(Debug, PartialEq)]
 /// DTED User Header Label (UHL)
 ///
@@ -83,12 +431,41 @@ pub enum RecognitionSentinel {
 /// * `accuracy`- absolute vertical accuracy in meters (with 90%
 ///   assurance that the linear errors will not exceed this value relative to
 ///   mean sea level)
+/// * `security_code` - security classification code, None if blank
+/// * `reference` - unique reference number, None if blank
 /// * `count` - number of longitude lines and latitude points
+/// * `multiple_accuracy` - whether or not the ACC record contains
+///   multiple accuracy sub-regions
 pub struct RawDTEDHeader {
     pub origin: AxisElement<Angle>,
     pub interval_secs_x_10: AxisElement<u16>,
     pub accuracy: Option<u16>,
+    pub security_code: Option<SecurityCode>,
+    pub reference: Option<String>,
     pub count: AxisElement<u16>,
+    pub multiple_accuracy: bool,
+}
+impl RawDTEDHeader {
+    /// Length of each data record (longitude line) in bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::parsers::dted_uhl_parser;
+    /// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let (_, header) = dted_uhl_parser(&raw).unwrap();
+    /// assert_eq!(header.record_len(), 12 + 2 * 121);
+    /// assert_eq!(header.file_len(), raw.len());
+    /// ```
+    pub fn record_len(&self) -> usize {
+        DT2_RECORD_OVERHEAD_LENGTH + 2 * self.count.lat as usize
+    }
+
+    /// Length of the entire DTED file in bytes, including
+    /// the UHL, DSI and ACC records, and all data records
+    pub fn file_len(&self) -> usize {
+        DT2_HEADER_LENGTH + self.count.lon as usize * self.record_len()
+    }
 }
 
 #[derive(Clone)]
@@ -96,7 +473,7 @@ pub struct RawDTEDHeader {
 ///
 /// # Fields
 ///
-/// * `filename` - filename
+/// * `filename` - filename, None if not read from a path
 /// * `origin` - position of the lower left corner of the grid (floating point precision)
 /// * `origin_angle` - position of the lower left corner of the grid
 /// * `interval` - interval (floating point precision)
@@ -104,15 +481,35 @@ pub struct RawDTEDHeader {
 /// * `accuracy` - absolute vertical accuracy in meters (with 90%
 ///   assurance that the linear errors will not exceed this value relative to
 ///   mean sea level)
+/// * `security_code` - security classification code, None if blank
+/// * `reference` - unique reference number, None if blank
 /// * `count` - number of longitude lines and latitude points
+/// * `multiple_accuracy` - whether or not the [DTEDRecordACC] contains
+///   multiple accuracy sub-regions
+/// * `dsi` - [DTEDRecordDSI], if present
+/// * `acc` - [DTEDRecordACC], if present
+/// * `level` - [DTEDLevel] derived from the UHL intervals and counts,
+///   None if they do not match any level
+/// * `warnings` - [LevelWarning]s found when the file was read, see
+///   [DTEDMetadata::level_warnings]
+///
+/// The header records as read are kept, see [DTEDMetadata::header_bytes]
 pub struct DTEDMetadata {
-    pub filename: String,
+    pub filename: Option<String>,
     pub origin: AxisElement<f64>,
     pub origin_angle: AxisElement<Angle>,
     pub interval: AxisElement<f64>,
     pub interval_secs: AxisElement<f32>,
     pub accuracy: Option<u16>,
+    pub security_code: Option<SecurityCode>,
+    pub reference: Option<String>,
     pub count: AxisElement<u16>,
+    pub multiple_accuracy: bool,
+    pub dsi: Option<DTEDRecordDSI>,
+    pub acc: Option<DTEDRecordACC>,
+    pub level: Option<DTEDLevel>,
+    pub warnings: Vec<LevelWarning>,
+    pub(crate) header_bytes: Option<Vec<u8>>,
 }
 impl DTEDMetadata {
     /// Create a [DTEDMetadata] from a [RawDTEDHeader]
@@ -120,24 +517,365 @@ impl DTEDMetadata {
     /// # Arguments
     ///
     /// * `raw` - [RawDTEDHeader]
-    /// * `fname` - filename
+    /// * `fname` - filename, if read from a path
     ///
     /// # Returns
     ///
     /// * [DTEDMetadata]: DTED metadata
-    pub fn from_header(raw: &RawDTEDHeader, fname: &str) -> DTEDMetadata {
+    pub fn from_header(raw: &RawDTEDHeader, fname: Option<&str>) -> DTEDMetadata {
         DTEDMetadata {
-            filename: fname.to_string(),
+            filename: fname.map(str::to_string),
             origin: raw.origin.into(),
             origin_angle: raw.origin,
             interval: raw.interval_secs_x_10 / (primitives::SEC2DEG * 10.0),
             interval_secs: raw.interval_secs_x_10 / 10.0,
             accuracy: raw.accuracy,
+            security_code: raw.security_code,
+            reference: raw.reference.clone(),
             count: raw.count,
+            multiple_accuracy: raw.multiple_accuracy,
+            dsi: None,
+            acc: None,
+            level: DTEDLevel::from_spacing(raw.interval_secs_x_10.lat as f32 / 10.0, raw.count.lat),
+            warnings: Vec::new(),
+            header_bytes: None,
+        }
+    }
+
+    /// Check that the [DTEDLevel] derived from the UHL agrees with the
+    /// level designator of the [DTEDRecordDSI] and the file extension,
+    /// and that the longitude interval and count match the [LatitudeZone]
+    /// of the cell (see [DTEDLevel::grid])
+    ///
+    /// Sources which are not available (no DSI record, no filename,
+    /// or not a DTED extension) are not checked. The warnings are
+    /// computed when a file is read, see [DTEDMetadata::warnings]
+    ///
+    /// # Returns
+    ///
+    /// * [LevelWarning]s, empty if all sources agree
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::{ DTEDLevel, LevelWarning };
+    ///
+    /// let metadata = DTEDData::read_header("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(metadata.level, Some(DTEDLevel::Level0));
+    /// assert_eq!(
+    ///     metadata.level_warnings(),
+    ///     vec![LevelWarning::ExtensionMismatch {
+    ///         uhl: DTEDLevel::Level0,
+    ///         extension: DTEDLevel::Level2,
+    ///     }],
+    /// );
+    /// assert_eq!(metadata.warnings, metadata.level_warnings());
+    /// ```
+    pub fn level_warnings(&self) -> Vec<LevelWarning> {
+        let uhl = match self.level {
+            Some(level) => level,
+            None => return vec![LevelWarning::Unknown],
+        };
+        let mut warnings = Vec::new();
+        // --------------------------------------------------
+        // the DSI record describes the entire cell, even when
+        // only a window of the data has been read
+        // --------------------------------------------------
+        let grid = uhl.grid(self.origin.lat, self.origin.lon);
+        let lon_count = self.dsi.as_ref().map_or(self.count.lon, |dsi| dsi.count.lon);
+        if self.interval_secs.lon != grid.interval_secs.lon || lon_count != grid.count.lon {
+            warnings.push(LevelWarning::LonSpacingMismatch { level: uhl, zone: grid.zone });
+        }
+        let dsi = self.dsi.as_ref().and_then(|dsi| dsi.version.as_deref());
+        if let Some(dsi) = dsi.and_then(DTEDLevel::from_designator) {
+            if dsi != uhl {
+                warnings.push(LevelWarning::DSIMismatch { uhl, dsi });
+            }
+        }
+        if let Some(extension) = self.filename.as_ref().and_then(DTEDLevel::from_path) {
+            if extension != uhl {
+                warnings.push(LevelWarning::ExtensionMismatch { uhl, extension });
+            }
+        }
+        warnings
+    }
+
+    /// Raw bytes of the UHL, DSI and ACC records as read
+    ///
+    /// Used by [DTEDData::write] to preserve reserved fields, and the
+    /// exact formatting of fields which have not been modified
+    ///
+    /// # Returns
+    ///
+    /// * Up to [DT2_HEADER_LENGTH] bytes of the header records (fewer if
+    ///   the DSI or ACC records are absent), or None if not read from a file
+    pub fn header_bytes(&self) -> Option<&[u8]> {
+        self.header_bytes.as_deref()
+    }
+
+    /// Discard the header records as read, such that [DTEDData::write]
+    /// writes every field of the header records from the metadata
+    pub fn clear_header_bytes(&mut self) {
+        self.header_bytes = None;
+    }
+
+    /// Set the filename of the file read, and validate the metadata
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path to the DTED file
+    pub(crate) fn set_filename(&mut self, path: &Path) {
+        self.filename = Some(path.to_string_lossy().to_string());
+        self.validate();
+    }
+
+    /// Store the [LevelWarning]s of the metadata, see [DTEDMetadata::level_warnings]
+    pub(crate) fn validate(&mut self) {
+        self.warnings = self.level_warnings();
+    }
+
+    /// Metadata of a window of the data, see [DTEDWindow::indices]
+    ///
+    /// The origin and count describe the window, while
+    /// the header records describe the entire file
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - ranges of latitude points and longitude lines
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata of the window
+    fn window(mut self, indices: &AxisElement<Range<usize>>) -> DTEDMetadata {
+        let offset = AxisElement::new(indices.lat.start as f64, indices.lon.start as f64);
+        self.origin = self.origin + offset * self.interval;
+        let origin_secs = AxisElement::new(
+            self.origin_angle.lat.total_secs(),
+            self.origin_angle.lon.total_secs(),
+        );
+        let interval_secs: AxisElement<f64> =
+            AxisElement::new(self.interval_secs.lat as f64, self.interval_secs.lon as f64);
+        let origin_secs = origin_secs + offset * interval_secs;
+        self.origin_angle = AxisElement::new(
+            Angle::from_secs(origin_secs.lat),
+            Angle::from_secs(origin_secs.lon),
+        );
+        self.count = AxisElement::new(indices.lat.len() as u16, indices.lon.len() as u16);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Elevation at a point, which may be void (no data)
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{ Elevation, DT2_VOID };
+///
+/// assert_eq!(Elevation::from_post(12), Elevation::Value(12.0));
+/// assert_eq!(Elevation::from_post(DT2_VOID), Elevation::Void);
+/// assert_eq!(Elevation::Void.value(), None);
+/// ```
+pub enum Elevation {
+    /// Elevation in meters
+    Value(f64),
+    /// No data, see [DT2_VOID]
+    Void,
+}
+impl Elevation {
+    /// Create an [Elevation] from the raw value of a post
+    ///
+    /// # Arguments
+    ///
+    /// * `post` - elevation of a post in meters, or [DT2_VOID]
+    ///
+    /// # Returns
+    ///
+    /// * [Elevation]
+    pub fn from_post(post: i16) -> Elevation {
+        match post {
+            DT2_VOID => Elevation::Void,
+            elev => Elevation::Value(elev as f64),
+        }
+    }
+
+    /// Elevation in meters, or None if void
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Elevation::Value(elev) => Some(*elev),
+            Elevation::Void => None,
         }
     }
+
+    /// Whether or not the elevation is void
+    pub fn is_void(&self) -> bool {
+        matches!(self, Elevation::Void)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Interpolation between posts, see [DTEDData::get_elevation_with]
+///
+/// Void posts ([DT2_VOID]) never contribute to the interpolated elevation.
+/// Beyond the edges of the data, the edge posts are repeated
+pub enum Interpolation {
+    /// Elevation of the nearest post, without interpolation
+    Nearest,
+    /// Bilinear interpolation of the 4 surrounding posts, renormalizing
+    /// the weights of the posts which are not void
+    #[default]
+    Bilinear,
+    /// Bicubic (Catmull-Rom) interpolation of the 16 surrounding posts, with
+    /// continuous first derivatives. Falls back to [Interpolation::Bilinear]
+    /// where any of the posts is void
+    Bicubic,
+    /// Lanczos interpolation (with 3 lobes) of the 36 surrounding posts.
+    /// Falls back to [Interpolation::Bilinear] where any of the posts is void
+    Lanczos,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// How the checksum of each data record is verified when reading DTED data
+pub enum ChecksumMode {
+    /// Checksums are not verified
+    Ignore,
+    /// Checksums are verified, and the indices of the columns (longitude
+    /// lines) with invalid checksums are collected in [DTEDData::invalid_checksums]
+    #[default]
+    Warn,
+    /// Checksums are verified, and the first invalid checksum is returned as an error
+    Fail,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Region of interest when reading DTED data, see [DTEDReadOptions::window]
+///
+/// Both variants are inclusive, and are clamped to the extent of the data.
+/// The resulting window always contains at least 2 posts along each axis
+/// (if the data does), such that it can be interpolated
+///
+/// # Examples
+///
+/// ```
+/// use dted2::DTEDData;
+/// use dted2::dted::{ DTEDReadOptions, DTEDWindow };
+/// use dted2::primitives::AxisElement;
+///
+/// let options = DTEDReadOptions {
+///     window: Some(DTEDWindow::Bounds {
+///         min: AxisElement::new(-29.75, -177.75),
+///         max: AxisElement::new(-29.5, -177.5),
+///     }),
+///     ..Default::default()
+/// };
+/// let data = DTEDData::read_with_options("tests/test_data_negative.dt2", &options).unwrap();
+/// assert_eq!(data.metadata.count, AxisElement::new(31, 31));
+/// assert_eq!(data.min, AxisElement::new(-29.75, -177.75));
+/// assert!(data.get_elevation(-29.6, -177.6).is_some());
+/// assert!(data.get_elevation(-29.9, -177.9).is_none());
+/// ```
+pub enum DTEDWindow {
+    /// Bounding box in degrees, expanded outwards to the nearest posts
+    Bounds {
+        min: AxisElement<f64>,
+        max: AxisElement<f64>,
+    },
+    /// Grid indices, where `lat` is the index of the latitude
+    /// point and `lon` is the index of the longitude line
+    Indices {
+        start: AxisElement<usize>,
+        end: AxisElement<usize>,
+    },
+}
+impl DTEDWindow {
+    /// Resolve the window to ranges of grid indices of a DTED file
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - [DTEDMetadata] of the full DTED file
+    ///
+    /// # Returns
+    ///
+    /// * Ranges of latitude points and longitude lines (exclusive end),
+    ///   or None if the window does not intersect the data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::DTEDWindow;
+    /// use dted2::primitives::AxisElement;
+    ///
+    /// let metadata = DTEDData::read_header("tests/test_data_negative.dt2").unwrap();
+    /// let window = DTEDWindow::Indices {
+    ///     start: AxisElement::new(10, 100),
+    ///     end: AxisElement::new(20, 500),
+    /// };
+    /// assert_eq!(window.indices(&metadata), Some(AxisElement::new(10..21, 100..121)));
+    /// ```
+    pub fn indices(&self, metadata: &DTEDMetadata) -> Option<AxisElement<Range<usize>>> {
+        let count = metadata.count;
+        let (start, end) = match *self {
+            DTEDWindow::Bounds { min, max } => {
+                // small tolerance, such that bounds on a post do not pick up a neighbour
+                const EPS: f64 = 1e-6;
+                let lo = (min - metadata.origin) / metadata.interval;
+                let hi = (max - metadata.origin) / metadata.interval;
+                let last = (count - 1) * 1.0;
+                let outside = hi.lat < -EPS
+                    || hi.lon < -EPS
+                    || lo.lat > last.lat + EPS
+                    || lo.lon > last.lon + EPS;
+                if outside {
+                    return None;
+                }
+                let floor = |x: f64| (x + EPS).floor().max(0.0) as usize;
+                let ceil = |x: f64| (x - EPS).ceil().max(0.0) as usize;
+                (
+                    AxisElement::new(floor(lo.lat), floor(lo.lon)),
+                    AxisElement::new(ceil(hi.lat), ceil(hi.lon)),
+                )
+            }
+            DTEDWindow::Indices { start, end } => (start, end),
+        };
+        Some(AxisElement::new(
+            window_range(start.lat, end.lat, count.lat as usize)?,
+            window_range(start.lon, end.lon, count.lon as usize)?,
+        ))
+    }
+}
+
+/// Clamp an inclusive range of indices to `0..len`,
+/// expanding it to contain at least 2 indices
+fn window_range(start: usize, end: usize, len: usize) -> Option<Range<usize>> {
+    if start > end || start >= len {
+        return None;
+    }
+    let end = end.min(len - 1) + 1;
+    match end - start {
+        1 if end < len => Some(start..end + 1),
+        1 if start > 0 => Some(start - 1..end),
+        _ => Some(start..end),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Options for reading DTED data, see [DTEDData::read_with_options]
+///
+/// # Fields
+///
+/// * `checksum` - [ChecksumMode], defaults to [ChecksumMode::Warn]
+/// * `window` - [DTEDWindow] to read, None to read all data. Only the data
+///   records within the window are read, and only the elevations within the
+///   window are decoded. Checksums are verified for the records read
+pub struct DTEDReadOptions {
+    pub checksum: ChecksumMode,
+    pub window: Option<DTEDWindow>,
 }
 
+#[derive(Clone)]
 /// DTED Data
 ///
 /// This is the main entry point for reading DTED files.
@@ -149,18 +887,21 @@ impl DTEDMetadata {
 /// * `min` - minimum lat/lon
 /// * `max` - maximum lat/lon
 /// * `data` - data
+/// * `invalid_checksums` - indices of the data records (columns) with
+///   invalid checksums, see [ChecksumMode::Warn]
 pub struct DTEDData {
     pub metadata: DTEDMetadata,
     pub min: AxisElement<f64>,
     pub max: AxisElement<f64>,
     pub data: Vec<RawDTEDRecord>,
+    pub invalid_checksums: Vec<usize>,
 }
 impl DTEDData {
     /// Read a DTED file
     ///
     /// # Arguments
     ///
-    /// * `path` (str): Path to the DTED file
+    /// * `path`: Path to the DTED file
     ///
     /// # Returns
     ///
@@ -172,113 +913,827 @@ impl DTEDData {
     /// use dted2::DTEDData;
     /// assert!(DTEDData::read("tests/test_data.dt2").is_ok());
     /// ```
-    pub fn read(path: &str) -> Result<DTEDData, DTEDError> {
-        let mut file = std::fs::File::open(path)?;
-        let mut content = Vec::new();
-        file.read_to_end(&mut content)?;
-        match parsers::dted_file_parser(&content) {
-            Ok((_, data)) => {
-                let metadata = DTEDMetadata::from_header(&data.header, path);
-                let interval = metadata.interval;
-                let origin_f64: AxisElement<f64> = data.header.origin.into();
-                Ok(DTEDData {
-                    metadata,
-                    min: origin_f64,
-                    max: origin_f64 + ((data.header.count - 1) * interval),
-                    data: data.data,
-                })
-            }
-            Err(e) => match e {
-                nom::Err::Incomplete(e) => Err(e.into()),
-                nom::Err::Error(e) | nom::Err::Failure(e) => Err(e.code.into()),
-            },
-        }
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<DTEDData, DTEDError> {
+        Self::read_with_options(path, &DTEDReadOptions::default())
     }
 
-    /// Read the header from a DTED file
+    /// Read a DTED file using [DTEDReadOptions]
     ///
     /// # Arguments
     ///
-    /// * `path` (str): Path to the DTED file
+    /// * `path`: Path to the DTED file
+    /// * `options` - [DTEDReadOptions]
     ///
     /// # Returns
     ///
-    /// * [DTEDMetadata]: DTED metadata
+    /// * [DTEDData]: DTED data
     ///
     /// # Examples
     ///
     /// ```
     /// use dted2::DTEDData;
-    /// assert!(DTEDData::read_header("tests/test_data.dt2").is_ok());
+    /// use dted2::dted::{ ChecksumMode, DTEDReadOptions };
+    ///
+    /// let options = DTEDReadOptions { checksum: ChecksumMode::Fail, ..Default::default() };
+    /// let data = DTEDData::read_with_options("tests/test_data_negative.dt2", &options).unwrap();
+    /// assert!(data.invalid_checksums.is_empty());
     /// ```
-    pub fn read_header(path: &str) -> Result<DTEDMetadata, DTEDError> {
-        let mut file = std::fs::File::open(path)?;
-        let mut content = Vec::new();
-        file.read_to_end(&mut content)?;
-        match parsers::dted_uhl_parser(&content) {
-            Ok((_, header)) => Ok(DTEDMetadata::from_header(&header, path)),
-            Err(e) => match e {
-                nom::Err::Incomplete(e) => Err(e.into()),
-                nom::Err::Error(e) | nom::Err::Failure(e) => Err(e.code.into()),
-            },
-        }
+    pub fn read_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &DTEDReadOptions,
+    ) -> Result<DTEDData, DTEDError> {
+        let mut data = match options.window {
+            Some(_) => {
+                Self::from_seekable_with_options(std::fs::File::open(path.as_ref())?, options)?
+            }
+            None => Self::from_bytes_with_options(&std::fs::read(path.as_ref())?, options)?,
+        };
+        data.metadata.set_filename(path.as_ref());
+        Ok(data)
     }
 
-    /// Get the elevation at a lat/lon
+    /// Read DTED data from a byte slice (e.g. an embedded asset
+    /// or a network-fetched buffer)
     ///
     /// # Arguments
     ///
-    /// * `lat` - latitude
-    /// * `lon` - longitude
+    /// * `content` - bytes of the DTED file, starting at the UHL
     ///
     /// # Returns
     ///
-    /// * Elevation (in meters) or None if out of bounds
+    /// * [DTEDData]: DTED data, without a filename
     ///
     /// # Examples
     ///
     /// ```
     /// use dted2::DTEDData;
-    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
-    /// assert!(dted_data.get_elevation(42.52, 15.75).is_some());
-    /// assert!(dted_data.get_elevation(0.0, 0.0).is_none());
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let data = DTEDData::from_bytes(&content).unwrap();
+    /// assert!(data.metadata.filename.is_none());
     /// ```
-    pub fn get_elevation<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+    pub fn from_bytes(content: &[u8]) -> Result<DTEDData, DTEDError> {
+        Self::from_bytes_with_options(content, &DTEDReadOptions::default())
+    }
+
+    /// Read DTED data from a byte slice using [DTEDReadOptions]
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - bytes of the DTED file, starting at the UHL
+    /// * `options` - [DTEDReadOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, without a filename
+    pub fn from_bytes_with_options(
+        content: &[u8],
+        options: &DTEDReadOptions,
+    ) -> Result<DTEDData, DTEDError> {
+        if options.window.is_some() {
+            return Self::from_seekable_with_options(std::io::Cursor::new(content), options);
+        }
+        match parsers::dted_file_parser(content) {
+            Ok((_, data)) => {
+                verify_counts(&data.header, data.dsi_record.as_ref())?;
+                let invalid_checksums = verify_checksums(
+                    &content[DT2_HEADER_LENGTH..data.header.file_len()],
+                    data.header.record_len(),
+                    0,
+                    options.checksum,
+                )?;
+                let mut metadata = DTEDMetadata::from_header(&data.header, None);
+                metadata.dsi = data.dsi_record;
+                metadata.acc = data.acc_record;
+                metadata.header_bytes = Some(content[..DT2_HEADER_LENGTH].to_vec());
+                metadata.validate();
+                let interval = metadata.interval;
+                let origin_f64: AxisElement<f64> = data.header.origin.into();
+                Ok(DTEDData {
+                    metadata,
+                    min: origin_f64,
+                    max: origin_f64 + ((data.header.count - 1) * interval),
+                    data: data.data,
+                    invalid_checksums,
+                })
+            }
+            Err(e) => Err(DTEDError::from_nom(content, e)),
+        }
+    }
+
+    /// Read DTED data from any [Read] source (e.g. an entry within a tarball)
+    ///
+    /// Reading starts at the current position of the source, and only the
+    /// bytes of the DTED file are read (the length is determined from the UHL),
+    /// leaving any trailing bytes of the source unread
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] source, positioned at the UHL
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, without a filename
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let file = std::fs::File::open("tests/test_data_negative.dt2").unwrap();
+    /// assert!(DTEDData::from_reader(file).is_ok());
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<DTEDData, DTEDError> {
+        Self::from_reader_with_options(reader, &DTEDReadOptions::default())
+    }
+
+    /// Read DTED data from any [Read] source using [DTEDReadOptions],
+    /// see [DTEDData::from_reader]
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] source, positioned at the UHL
+    /// * `options` - [DTEDReadOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, without a filename
+    pub fn from_reader_with_options<R: Read>(
+        mut reader: R,
+        options: &DTEDReadOptions,
+    ) -> Result<DTEDData, DTEDError> {
+        if let Some(window) = &options.window {
+            return Self::window_from_reader(reader, window, options, |reader, len| {
+                std::io::copy(&mut reader.take(len as u64), &mut std::io::sink()).map(|_| ())
+            });
+        }
         // --------------------------------------------------
-        // get the indices + fractions
+        // read the UHL to determine the length of the file
         // --------------------------------------------------
-        let (lat_idx, lon_idx) = self.get_indices(lat, lon)?;
-        let mut lat_int = lat_idx as usize;
-        let mut lon_int = lon_idx as usize;
-        let mut lat_frac = lat_idx - lat_int as f64;
-        let mut lon_frac = lon_idx - lon_int as f64;
+        let mut content = Vec::with_capacity(DT2_UHL_LENGTH as usize);
+        reader.by_ref().take(DT2_UHL_LENGTH).read_to_end(&mut content)?;
+        let len = match parsers::dted_uhl_parser(&content) {
+            Ok((_, header)) => header.file_len(),
+            Err(e) => return Err(DTEDError::from_nom(&content, e)),
+        };
         // --------------------------------------------------
-        // handle the edge case of max lat/lon
+        // read the rest of the file
         // --------------------------------------------------
-        if lat_int == self.metadata.count.lat as usize - 1 {
-            lat_int -= 1;
-            lat_frac += 1.0;
-        }
-        if lon_int == self.metadata.count.lon as usize - 1 {
-            lon_int -= 1;
-            lon_frac += 1.0;
+        content.reserve_exact(len - content.len());
+        reader
+            .take((len - content.len()) as u64)
+            .read_to_end(&mut content)?;
+        Self::from_bytes_with_options(&content, options)
+    }
+
+    /// Read DTED data from a [Read] + [Seek] source (e.g. a [std::io::Cursor]
+    /// or an already opened file), starting at the beginning of the
+    /// source regardless of its current position
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] + [Seek] source
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, without a filename
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{ Cursor, Seek, SeekFrom };
+    /// use dted2::DTEDData;
+    ///
+    /// let mut cursor = Cursor::new(std::fs::read("tests/test_data_negative.dt2").unwrap());
+    /// cursor.seek(SeekFrom::End(0)).unwrap();
+    /// assert!(DTEDData::from_seekable(&mut cursor).is_ok());
+    /// ```
+    pub fn from_seekable<R: Read + Seek>(reader: R) -> Result<DTEDData, DTEDError> {
+        Self::from_seekable_with_options(reader, &DTEDReadOptions::default())
+    }
+
+    /// Read DTED data from a [Read] + [Seek] source using [DTEDReadOptions],
+    /// see [DTEDData::from_seekable]
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] + [Seek] source
+    /// * `options` - [DTEDReadOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data, without a filename
+    pub fn from_seekable_with_options<R: Read + Seek>(
+        mut reader: R,
+        options: &DTEDReadOptions,
+    ) -> Result<DTEDData, DTEDError> {
+        reader.seek(SeekFrom::Start(0))?;
+        match &options.window {
+            Some(window) => Self::window_from_reader(reader, window, options, |reader, len| {
+                reader.seek(SeekFrom::Current(len as i64)).map(|_| ())
+            }),
+            None => Self::from_reader_with_options(reader, options),
         }
+    }
+
+    /// Read a [DTEDWindow] of DTED data from a [Read] source,
+    /// positioned at the UHL
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] source, positioned at the UHL
+    /// * `window` - [DTEDWindow] to read
+    /// * `options` - [DTEDReadOptions]
+    /// * `skip` - skips `len` bytes of the source
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData]: DTED data within the window, without a filename
+    fn window_from_reader<R: Read>(
+        mut reader: R,
+        window: &DTEDWindow,
+        options: &DTEDReadOptions,
+        skip: impl FnOnce(&mut R, usize) -> std::io::Result<()>,
+    ) -> Result<DTEDData, DTEDError> {
+        // --------------------------------------------------
+        // read the header records
         // --------------------------------------------------
-        // values for the 4 corners for bilinear interpolation
+        let (header, dsi_record, acc_record, header_bytes) =
+            header_records_from_reader(&mut reader)?;
+        let mut metadata = DTEDMetadata::from_header(&header, None);
+        metadata.dsi = Some(dsi_record);
+        metadata.acc = Some(acc_record);
+        metadata.header_bytes = Some(header_bytes);
+        metadata.validate();
+        let indices = window.indices(&metadata).ok_or(DTEDError::EmptyWindow)?;
         // --------------------------------------------------
-        let elev00 = self.data[lon_int].elevations[lat_int] as f64;
-        let elev01 = self.data[lon_int].elevations[lat_int + 1] as f64;
-        let elev10 = self.data[lon_int + 1].elevations[lat_int] as f64;
-        let elev11 = self.data[lon_int + 1].elevations[lat_int + 1] as f64;
+        // read only the data records within the window
+        // --------------------------------------------------
+        let record_len = header.record_len();
+        let start = DT2_HEADER_LENGTH + indices.lon.start * record_len;
+        let len = indices.lon.len() * record_len;
+        skip(&mut reader, start - DT2_HEADER_LENGTH)?;
+        let mut content = Vec::with_capacity(len);
+        reader.take(len as u64).read_to_end(&mut content)?;
+        if content.len() < len {
+            let offset = start + content.len();
+            return Err(DTEDError::Truncated {
+                section: Section::from_offset(offset, Some(header.count.lat)),
+                offset,
+            });
+        }
+        let invalid_checksums =
+            verify_checksums(&content, record_len, indices.lon.start, options.checksum)?;
         // --------------------------------------------------
-        // return interpolated value
+        // decode only the elevations within the window
         // --------------------------------------------------
-        let result = 0.0
-            + elev00 * (1.0 - lon_frac) * (1.0 - lat_frac)
-            + elev01 * (1.0 - lon_frac) * lat_frac
-            + elev10 * lon_frac * (1.0 - lat_frac)
-            + elev11 * lon_frac * lat_frac;
-        Some(result)
+        let lat_count = header.count.lat;
+        let parsed = nom::multi::count(
+            |input| parsers::parse_dted_record_rows(input, lat_count as usize, indices.lat.clone()),
+            indices.lon.len(),
+        )(&content);
+        let data = match parsed {
+            Ok((_, data)) => data,
+            Err(e) => return Err(DTEDError::from_nom_at(&content, start, Some(lat_count), e)),
+        };
+        let metadata = metadata.window(&indices);
+        let min = metadata.origin;
+        let max = min + ((metadata.count - 1) * metadata.interval);
+        Ok(DTEDData {
+            metadata,
+            min,
+            max,
+            data,
+            invalid_checksums,
+        })
+    }
+
+    /// Stream the data records (longitude lines) from any [Read] source,
+    /// one at a time, see [DTEDRecords]
+    ///
+    /// Only the header records are read by this function
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] source, positioned at the UHL (e.g. a pipe)
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDRecords]: iterator over the data records
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let file = std::fs::File::open("tests/test_data_negative.dt2").unwrap();
+    /// let records = DTEDData::records(std::io::BufReader::new(file)).unwrap();
+    /// assert_eq!(records.metadata.count.lon, 121);
+    /// let max = records
+    ///     .map(|record| *record.unwrap().elevations.iter().max().unwrap())
+    ///     .max();
+    /// assert_eq!(max, Some(466));
+    /// ```
+    pub fn records<R: Read>(reader: R) -> Result<DTEDRecords<R>, DTEDError> {
+        Self::records_with_options(reader, &DTEDReadOptions::default())
+    }
+
+    /// Stream the data records (longitude lines) from any [Read] source
+    /// using [DTEDReadOptions], see [DTEDData::records]
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] source, positioned at the UHL (e.g. a pipe)
+    /// * `options` - [DTEDReadOptions]. With a [DTEDWindow], only the
+    ///   records and rows within the window are yielded
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDRecords]: iterator over the data records
+    pub fn records_with_options<R: Read>(
+        mut reader: R,
+        options: &DTEDReadOptions,
+    ) -> Result<DTEDRecords<R>, DTEDError> {
+        let (header, dsi_record, acc_record, header_bytes) =
+            header_records_from_reader(&mut reader)?;
+        let mut metadata = DTEDMetadata::from_header(&header, None);
+        metadata.dsi = Some(dsi_record);
+        metadata.acc = Some(acc_record);
+        metadata.header_bytes = Some(header_bytes);
+        metadata.validate();
+        let indices = match &options.window {
+            Some(window) => window.indices(&metadata).ok_or(DTEDError::EmptyWindow)?,
+            None => AxisElement::new(0..header.count.lat as usize, 0..header.count.lon as usize),
+        };
+        Ok(DTEDRecords {
+            metadata: metadata.window(&indices),
+            invalid_checksums: Vec::new(),
+            reader,
+            checksum: options.checksum,
+            lat_count: header.count.lat,
+            rows: indices.lat,
+            columns: indices.lon,
+            column: 0,
+            buffer: Vec::with_capacity(header.record_len()),
+        })
+    }
+
+    /// Read the header from a DTED file, including the
+    /// [DTEDRecordDSI] and [DTEDRecordACC] if present
+    ///
+    /// Only the header records are read from disk (at most
+    /// [DT2_HEADER_LENGTH] bytes), not the data records
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// assert!(DTEDData::read_header("tests/test_data.dt2").is_ok());
+    /// ```
+    pub fn read_header<P: AsRef<Path>>(path: P) -> Result<DTEDMetadata, DTEDError> {
+        let mut metadata = Self::header_from_reader(std::fs::File::open(path.as_ref())?)?;
+        metadata.set_filename(path.as_ref());
+        Ok(metadata)
+    }
+
+    /// Read only the User Header Label (UHL) from a DTED file,
+    /// which is the fastest way to get the footprint of a file
+    ///
+    /// Only the UHL is read from disk ([DT2_UHL_LENGTH] bytes),
+    /// so [DTEDMetadata::dsi] and [DTEDMetadata::acc] are None
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let metadata = DTEDData::read_uhl("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(metadata.count.lon, 121);
+    /// assert!(metadata.dsi.is_none());
+    /// ```
+    pub fn read_uhl<P: AsRef<Path>>(path: P) -> Result<DTEDMetadata, DTEDError> {
+        let mut metadata = Self::uhl_from_reader(std::fs::File::open(path.as_ref())?)?;
+        metadata.set_filename(path.as_ref());
+        Ok(metadata)
+    }
+
+    /// Read the header from any [Read] source, including the
+    /// [DTEDRecordDSI] and [DTEDRecordACC] if present
+    ///
+    /// Reading starts at the current position of the source, and
+    /// at most [DT2_HEADER_LENGTH] bytes are read
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] source, positioned at the UHL
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata, without a filename
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let file = std::fs::File::open("tests/test_data_negative.dt2").unwrap();
+    /// let metadata = DTEDData::header_from_reader(file).unwrap();
+    /// assert!(metadata.dsi.is_some());
+    /// assert!(metadata.acc.is_some());
+    /// ```
+    pub fn header_from_reader<R: Read>(reader: R) -> Result<DTEDMetadata, DTEDError> {
+        let mut content = Vec::with_capacity(DT2_HEADER_LENGTH);
+        reader
+            .take(DT2_HEADER_LENGTH as u64)
+            .read_to_end(&mut content)?;
+        Self::header_from_bytes(&content)
+    }
+
+    /// Read only the User Header Label (UHL) from any [Read] source
+    ///
+    /// Reading starts at the current position of the source, and
+    /// at most [DT2_UHL_LENGTH] bytes are read
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - [Read] source, positioned at the UHL
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata, without a filename
+    pub fn uhl_from_reader<R: Read>(reader: R) -> Result<DTEDMetadata, DTEDError> {
+        let mut content = Vec::with_capacity(DT2_UHL_LENGTH as usize);
+        reader.take(DT2_UHL_LENGTH).read_to_end(&mut content)?;
+        Self::header_from_bytes(&content)
+    }
+
+    /// Read the header from a byte slice, including the
+    /// [DTEDRecordDSI] and [DTEDRecordACC] if present
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - bytes of the DTED file, starting at the UHL. Only
+    ///   the UHL is required, the DSI and ACC records are optional
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMetadata]: DTED metadata, without a filename
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let content = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let metadata = DTEDData::header_from_bytes(&content[..80]).unwrap();
+    /// assert_eq!(metadata.count.lat, 121);
+    /// assert!(metadata.dsi.is_none());
+    /// ```
+    pub fn header_from_bytes(content: &[u8]) -> Result<DTEDMetadata, DTEDError> {
+        let parsed = tuple((
+            parsers::dted_uhl_parser,
+            parsers::opt_dted_dsi_parser,
+            parsers::opt_dted_acc_parser,
+        ))(content);
+        match parsed {
+            Ok((_, (header, dsi, acc))) => {
+                verify_counts(&header, dsi.as_ref())?;
+                let mut metadata = DTEDMetadata::from_header(&header, None);
+                metadata.dsi = dsi;
+                metadata.acc = acc;
+                let len = content.len().min(DT2_HEADER_LENGTH);
+                metadata.header_bytes = Some(content[..len].to_vec());
+                metadata.validate();
+                Ok(metadata)
+            }
+            Err(e) => Err(DTEDError::from_nom(content, e)),
+        }
+    }
+
+    /// Get the elevation at a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters), or None if out of bounds or void. Void posts
+    ///   ([DT2_VOID]) are excluded from the interpolation, falling back
+    ///   to the surrounding posts which are not void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let dted_data = DTEDData::read("tests/test_data.dt2").unwrap();
+    /// assert!(dted_data.get_elevation(42.52, 15.75).is_some());
+    /// assert!(dted_data.get_elevation(0.0, 0.0).is_none());
+    /// ```
+    pub fn get_elevation<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+        self.get_elevation_with(lat, lon, Interpolation::Bilinear)
+    }
+
+    /// Get the elevation at a lat/lon, using an [Interpolation]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `interpolation` - [Interpolation] between the posts
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters), or None if out of bounds or void
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::Interpolation;
+    ///
+    /// let dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let post = dted_data.data[60].elevations[30] as f64;
+    /// // 30 posts north, 60 posts east of the origin (30" interval)
+    /// let (lat, lon) = (-30.0 + 30.0 / 120.0, -178.0 + 60.0 / 120.0);
+    /// assert_eq!(dted_data.get_elevation_with(lat + 0.001, lon, Interpolation::Nearest), Some(post));
+    /// for interpolation in [Interpolation::Bilinear, Interpolation::Bicubic, Interpolation::Lanczos] {
+    ///     let elevation = dted_data.get_elevation_with(lat, lon, interpolation).unwrap();
+    ///     assert!((elevation - post).abs() < 1e-6);
+    /// }
+    /// assert!(dted_data.get_elevation_with(0.0, 0.0, Interpolation::Bicubic).is_none());
+    /// ```
+    pub fn get_elevation_with<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+        interpolation: Interpolation,
+    ) -> Option<f64> {
+        let indices = self.get_indices(lat, lon)?;
+        interpolate(self.metadata.count, indices, interpolation, |lat_int, lon_int| {
+            Elevation::from_post(self.data[lon_int].elevations[lat_int]).value()
+        })
+    }
+
+    /// Get the elevations at many lat/lons, given as separate slices
+    ///
+    /// Equivalent to [DTEDData::get_elevation_with] at each point. With the
+    /// `rayon` feature, the points are evaluated in parallel. Without it, the
+    /// cost per point is the same as calling [DTEDData::get_elevation_with] in
+    /// a loop, so only the `rayon` path is faster (see `benches/elevation.rs`)
+    ///
+    /// # Arguments
+    ///
+    /// * `lats` - latitudes
+    /// * `lons` - longitudes
+    /// * `interpolation` - [Interpolation] between the posts
+    /// * `out` - elevation (in meters) at each point, or None if out of bounds or void
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or [DTEDError::DimensionMismatch] if the slices differ in length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::Interpolation;
+    ///
+    /// let dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let lats = [-29.5, -29.25, 0.0];
+    /// let lons = [-177.5, -177.75, 0.0];
+    /// let mut elevations = [None; 3];
+    /// dted_data.get_elevations(&lats, &lons, Interpolation::Bilinear, &mut elevations).unwrap();
+    /// assert_eq!(elevations[0], dted_data.get_elevation(-29.5, -177.5));
+    /// assert_eq!(elevations[1], dted_data.get_elevation(-29.25, -177.75));
+    /// assert_eq!(elevations[2], None);
+    /// ```
+    pub fn get_elevations(
+        &self,
+        lats: &[f64],
+        lons: &[f64],
+        interpolation: Interpolation,
+        out: &mut [Option<f64>],
+    ) -> Result<(), DTEDError> {
+        check_len("longitudes", lats.len(), lons.len())?;
+        check_len("elevations", lats.len(), out.len())?;
+        self.batch(interpolation, out, |i| (lats[i], lons[i]));
+        Ok(())
+    }
+
+    /// Get the elevations at many lat/lons, given as [AxisElement]s
+    ///
+    /// Equivalent to [DTEDData::get_elevation_with] at each point. With the
+    /// `rayon` feature, the points are evaluated in parallel. Without it, the
+    /// cost per point is the same as calling [DTEDData::get_elevation_with] in
+    /// a loop, so only the `rayon` path is faster (see `benches/elevation.rs`)
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - latitudes and longitudes
+    /// * `interpolation` - [Interpolation] between the posts
+    /// * `out` - elevation (in meters) at each point, or None if out of bounds or void
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or [DTEDError::DimensionMismatch] if the slices differ in length
+    pub fn get_elevations_at(
+        &self,
+        points: &[AxisElement<f64>],
+        interpolation: Interpolation,
+        out: &mut [Option<f64>],
+    ) -> Result<(), DTEDError> {
+        check_len("elevations", points.len(), out.len())?;
+        self.batch(interpolation, out, |i| (points[i].lat, points[i].lon));
+        Ok(())
+    }
+
+    /// Evaluate the elevation at the `i`th point into `out[i]`
+    ///
+    /// The bounds, interval and count are read once per batch, while the
+    /// bounds check and interpolation of each point are those of
+    /// [DTEDData::get_elevation_with], such that the results are identical
+    fn batch(
+        &self,
+        interpolation: Interpolation,
+        out: &mut [Option<f64>],
+        point: impl Fn(usize) -> (f64, f64) + Sync,
+    ) {
+        let (min, max, interval) = (self.min, self.max, self.metadata.interval);
+        let count = self.metadata.count;
+        let elevation = |i: usize| {
+            let (lat, lon) = point(i);
+            let indices = grid_indices(min, max, interval, lat, lon)?;
+            interpolate(count, indices, interpolation, |lat_int, lon_int| {
+                Elevation::from_post(self.data[lon_int].elevations[lat_int]).value()
+            })
+        };
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            out.par_iter_mut()
+                .enumerate()
+                .with_min_len(BATCH_MIN_LEN)
+                .for_each(|(i, out)| *out = elevation(i));
+        }
+        #[cfg(not(feature = "rayon"))]
+        for (i, out) in out.iter_mut().enumerate() {
+            *out = elevation(i);
+        }
+    }
+
+    /// Get the elevation at a lat/lon, distinguishing
+    /// voids from points which are out of bounds
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [Elevation], see [DTEDData::get_elevation], or None if out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::{ Elevation, DT2_VOID };
+    ///
+    /// let mut dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// dted_data.data[60].elevations[60] = DT2_VOID;
+    /// assert_eq!(dted_data.get_elevation_checked(-29.5, -177.5), Some(Elevation::Void));
+    /// assert!(dted_data.get_elevation_checked(-29.501, -177.5).unwrap().value().is_some());
+    /// assert_eq!(dted_data.get_elevation_checked(0.0, 0.0), None);
+    /// ```
+    pub fn get_elevation_checked<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+    ) -> Option<Elevation> {
+        let indices = self.get_indices(lat, lon)?;
+        let elevation = bilinear(self.metadata.count, indices, |lat_int, lon_int| {
+            Elevation::from_post(self.data[lon_int].elevations[lat_int]).value()
+        });
+        Some(elevation.map_or(Elevation::Void, Elevation::Value))
+    }
+
+    /// Number of void posts ([DT2_VOID]) within the data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::DT2_VOID;
+    ///
+    /// let mut dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(dted_data.void_count(), 0);
+    /// dted_data.data[3].elevations[7] = DT2_VOID;
+    /// assert_eq!(dted_data.void_count(), 1);
+    /// ```
+    pub fn void_count(&self) -> usize {
+        self.data
+            .iter()
+            .map(|record| record.elevations.iter().filter(|&&elev| elev == DT2_VOID).count())
+            .sum()
+    }
+
+    /// Mask of the void posts ([DT2_VOID]) within the data
+    ///
+    /// # Returns
+    ///
+    /// * Mask indexed as `[lon_index][lat_index]`, the same as [DTEDData::data],
+    ///   where `true` marks a void post
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::DT2_VOID;
+    ///
+    /// let mut dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// dted_data.data[3].elevations[7] = DT2_VOID;
+    /// let mask = dted_data.void_mask();
+    /// assert!(mask[3][7]);
+    /// assert!(!mask[7][3]);
+    /// ```
+    pub fn void_mask(&self) -> Vec<Vec<bool>> {
+        self.data
+            .iter()
+            .map(|record| record.elevations.iter().map(|&elev| elev == DT2_VOID).collect())
+            .collect()
+    }
+
+    /// Get the accuracy at a lat/lon, taking into account
+    /// any accuracy sub-regions within the [DTEDRecordACC]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDAccuracy] or None if out of bounds. If there is no [DTEDRecordACC],
+    ///   only the absolute vertical accuracy from the UHL is populated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert!(dted_data.get_accuracy(-29.5, -177.5).is_some());
+    /// assert!(dted_data.get_accuracy(0.0, 0.0).is_none());
+    /// ```
+    pub fn get_accuracy<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<DTEDAccuracy> {
+        let lat: f64 = lat.into();
+        let lon: f64 = lon.into();
+        self.get_indices(lat, lon)?;
+        Some(match &self.metadata.acc {
+            Some(acc) => acc.accuracy_at(lat, lon),
+            None => DTEDAccuracy {
+                abs_vertical: self.metadata.accuracy,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Get the absolute vertical accuracy at a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Absolute vertical accuracy (in meters), falling back to the
+    ///   UHL accuracy, or None if out of bounds or not available
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let dted_data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// // neither the UHL nor the ACC record specify the accuracy
+    /// assert_eq!(dted_data.get_vertical_accuracy(-29.5, -177.5), None);
+    /// assert_eq!(dted_data.get_vertical_accuracy(0.0, 0.0), None);
+    /// ```
+    pub fn get_vertical_accuracy<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<u16> {
+        self.get_accuracy(lat, lon)?
+            .abs_vertical
+            .or(self.metadata.accuracy)
     }
 
     /// Get the indices of a lat/lon
@@ -301,72 +1756,708 @@ impl DTEDData {
     /// assert!(dted_data.get_indices(0.0, 0.0).is_none());
     /// ```
     pub fn get_indices<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<(f64, f64)> {
+        grid_indices(self.min, self.max, self.metadata.interval, lat.into(), lon.into())
+    }
+}
+
+/// Iterator over the data records (longitude lines) of a DTED file,
+/// parsing one record at a time from a [Read] source, see [DTEDData::records]
+///
+/// Memory usage is constant, as only a single record is buffered.
+/// Each record is yielded as a [RawDTEDRecord], or an error if the
+/// record cannot be read or parsed, after which iteration ends
+///
+/// # Fields
+///
+/// * `metadata` - [DTEDMetadata], describing the records yielded
+/// * `invalid_checksums` - indices of the records yielded so far with
+///   invalid checksums, see [ChecksumMode::Warn]
+pub struct DTEDRecords<R> {
+    pub metadata: DTEDMetadata,
+    pub invalid_checksums: Vec<usize>,
+    reader: R,
+    checksum: ChecksumMode,
+    lat_count: u16,
+    rows: Range<usize>,
+    columns: Range<usize>,
+    column: usize,
+    buffer: Vec<u8>,
+}
+impl<R: Read> DTEDRecords<R> {
+    /// Read and parse the next data record
+    ///
+    /// # Returns
+    ///
+    /// * [RawDTEDRecord] of the current column
+    fn read_record(&mut self) -> Result<RawDTEDRecord, DTEDError> {
+        let record_len = DT2_RECORD_OVERHEAD_LENGTH + 2 * self.lat_count as usize;
         // --------------------------------------------------
-        // check bounds
+        // skip any records before the window
         // --------------------------------------------------
-        let lat: f64 = lat.into();
-        let lon: f64 = lon.into();
-        if lat < self.min.lat || lat > self.max.lat || lon < self.min.lon || lon > self.max.lon {
+        if self.column < self.columns.start {
+            let len = (self.columns.start - self.column) * record_len;
+            std::io::copy(&mut self.reader.by_ref().take(len as u64), &mut std::io::sink())?;
+            self.column = self.columns.start;
+        }
+        // --------------------------------------------------
+        // read a single record
+        // --------------------------------------------------
+        let start = DT2_HEADER_LENGTH + self.column * record_len;
+        self.buffer.clear();
+        self.reader
+            .by_ref()
+            .take(record_len as u64)
+            .read_to_end(&mut self.buffer)?;
+        if self.buffer.len() < record_len {
+            return Err(DTEDError::Truncated {
+                section: Section::Data(self.column),
+                offset: start + self.buffer.len(),
+            });
+        }
+        if !verify_checksums(&self.buffer, record_len, self.column, self.checksum)?.is_empty() {
+            self.invalid_checksums.push(self.column - self.columns.start);
+        }
+        let lat_count = self.lat_count as usize;
+        let parsed = parsers::parse_dted_record_rows(&self.buffer, lat_count, self.rows.clone());
+        match parsed {
+            Ok((_, record)) => Ok(record),
+            Err(e) => Err(DTEDError::from_nom_at(&self.buffer, start, Some(self.lat_count), e)),
+        }
+    }
+}
+impl<R: Read> Iterator for DTEDRecords<R> {
+    type Item = Result<RawDTEDRecord, DTEDError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.column >= self.columns.end {
             return None;
         }
-        let lat_idx = (lat - self.min.lat) / self.metadata.interval.lat;
-        let lon_idx = (lon - self.min.lon) / self.metadata.interval.lon;
-        Some((lat_idx, lon_idx))
+        let record = self.read_record();
+        // --------------------------------------------------
+        // iteration ends after an error
+        // --------------------------------------------------
+        self.column = match record {
+            Ok(_) => self.column + 1,
+            Err(_) => self.columns.end,
+        };
+        Some(record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.columns.end - self.column.max(self.columns.start);
+        (0, Some(remaining))
     }
 }
 
-/// TODO
+/// Verify the length of a slice within a batch query
+///
+/// # Arguments
+///
+/// * `name` - name of the slice, see [DTEDError::DimensionMismatch]
+/// * `expected` - number of points
+/// * `found` - length of the slice
+fn check_len(name: &'static str, expected: usize, found: usize) -> Result<(), DTEDError> {
+    match expected == found {
+        true => Ok(()),
+        false => Err(DTEDError::DimensionMismatch { axis: name, expected, found }),
+    }
+}
+
+/// Get the (fractional) grid indices of a lat/lon
+///
+/// # Arguments
+///
+/// * `min` - minimum lat/lon of the grid
+/// * `max` - maximum lat/lon of the grid
+/// * `interval` - interval between posts (in degrees)
+/// * `lat` - latitude
+/// * `lon` - longitude
+///
+/// # Returns
+///
+/// * `(lat_index, lon_index)` or None if out of bounds
+pub(crate) fn grid_indices(
+    min: AxisElement<f64>,
+    max: AxisElement<f64>,
+    interval: AxisElement<f64>,
+    lat: f64,
+    lon: f64,
+) -> Option<(f64, f64)> {
+    // --------------------------------------------------
+    // check bounds
+    // --------------------------------------------------
+    if lat < min.lat || lat > max.lat || lon < min.lon || lon > max.lon {
+        return None;
+    }
+    let lat_idx = (lat - min.lat) / interval.lat;
+    let lon_idx = (lon - min.lon) / interval.lon;
+    Some((lat_idx, lon_idx))
+}
+
+/// Bilinear interpolation of the posts surrounding fractional grid indices
+///
+/// # Arguments
+///
+/// * `count` - number of longitude lines and latitude points
+/// * `(lat_idx, lon_idx)` - fractional grid indices, within bounds
+/// * `post` - elevation of the post at `(lat_index, lon_index)`, None if void
+///
+/// # Returns
+///
+/// * Interpolated elevation (in meters), from the surrounding posts which
+///   are not void, or None if every post contributing to the point is void
+pub(crate) fn bilinear(
+    count: AxisElement<u16>,
+    (lat_idx, lon_idx): (f64, f64),
+    post: impl Fn(usize, usize) -> Option<f64>,
+) -> Option<f64> {
+    // --------------------------------------------------
+    // get the integer indices + fractions
+    // --------------------------------------------------
+    let mut lat_int = lat_idx as usize;
+    let mut lon_int = lon_idx as usize;
+    let mut lat_frac = lat_idx - lat_int as f64;
+    let mut lon_frac = lon_idx - lon_int as f64;
+    // --------------------------------------------------
+    // handle the edge case of max lat/lon
+    // --------------------------------------------------
+    if lat_int == count.lat as usize - 1 {
+        lat_int -= 1;
+        lat_frac += 1.0;
+    }
+    if lon_int == count.lon as usize - 1 {
+        lon_int -= 1;
+        lon_frac += 1.0;
+    }
+    // --------------------------------------------------
+    // values and weights for the 4 corners for bilinear interpolation
+    // --------------------------------------------------
+    let corners = [
+        (post(lat_int, lon_int), (1.0 - lon_frac) * (1.0 - lat_frac)),
+        (post(lat_int + 1, lon_int), (1.0 - lon_frac) * lat_frac),
+        (post(lat_int, lon_int + 1), lon_frac * (1.0 - lat_frac)),
+        (post(lat_int + 1, lon_int + 1), lon_frac * lat_frac),
+    ];
+    // --------------------------------------------------
+    // return interpolated value, renormalizing the
+    // weights of the corners which are not void
+    // --------------------------------------------------
+    let (sum, weight) = corners
+        .iter()
+        .filter_map(|&(elev, weight)| elev.map(|elev| (elev * weight, weight)))
+        .fold((0.0, 0.0), |(sum, total), (elev, weight)| (sum + elev, total + weight));
+    if weight > 0.0 {
+        Some(sum / weight)
+    } else {
+        None
+    }
+}
+
+/// Interpolation of the posts surrounding fractional grid indices
+///
+/// # Arguments
+///
+/// * `count` - number of longitude lines and latitude points
+/// * `(lat_idx, lon_idx)` - fractional grid indices, within bounds
+/// * `interpolation` - [Interpolation] between the posts
+/// * `post` - elevation of the post at `(lat_index, lon_index)`, None if void
+///
+/// # Returns
+///
+/// * Interpolated elevation (in meters), or None if void
+pub(crate) fn interpolate(
+    count: AxisElement<u16>,
+    indices: (f64, f64),
+    interpolation: Interpolation,
+    post: impl Fn(usize, usize) -> Option<f64>,
+) -> Option<f64> {
+    match interpolation {
+        Interpolation::Nearest => {
+            let lat_int = (indices.0.round() as usize).min(count.lat as usize - 1);
+            let lon_int = (indices.1.round() as usize).min(count.lon as usize - 1);
+            post(lat_int, lon_int)
+        }
+        Interpolation::Bilinear => bilinear(count, indices, post),
+        Interpolation::Bicubic => convolve(count, indices, 2, catmull_rom, &post)
+            .or_else(|| bilinear(count, indices, &post)),
+        Interpolation::Lanczos => convolve(count, indices, LANCZOS_LOBES, lanczos, &post)
+            .or_else(|| bilinear(count, indices, &post)),
+    }
+}
+
+/// Number of lobes of the [Interpolation::Lanczos] kernel
+const LANCZOS_LOBES: usize = 3;
+
+/// Separable convolution of the posts surrounding fractional grid indices
+///
+/// # Arguments
 ///
+/// * `count` - number of longitude lines and latitude points
+/// * `(lat_idx, lon_idx)` - fractional grid indices, within bounds
+/// * `radius` - number of posts on either side contributing along each axis
+/// * `kernel` - weight of a post at a distance (in posts)
+/// * `post` - elevation of the post at `(lat_index, lon_index)`, None if void
+///
+/// # Returns
+///
+/// * Interpolated elevation (in meters), with the weights normalized,
+///   or None if any post with a nonzero weight is void
+fn convolve(
+    count: AxisElement<u16>,
+    (lat_idx, lon_idx): (f64, f64),
+    radius: usize,
+    kernel: fn(f64) -> f64,
+    post: impl Fn(usize, usize) -> Option<f64>,
+) -> Option<f64> {
+    let radius = radius as isize;
+    let lat_floor = lat_idx.floor() as isize;
+    let lon_floor = lon_idx.floor() as isize;
+    // edge posts are repeated beyond the edges of the data
+    let clamp = |idx: isize, count: u16| idx.clamp(0, count as isize - 1) as usize;
+    let (mut sum, mut total) = (0.0, 0.0);
+    for lat in lat_floor + 1 - radius..=lat_floor + radius {
+        let lat_weight = kernel(lat_idx - lat as f64);
+        for lon in lon_floor + 1 - radius..=lon_floor + radius {
+            let weight = lat_weight * kernel(lon_idx - lon as f64);
+            if weight == 0.0 {
+                continue;
+            }
+            sum += weight * post(clamp(lat, count.lat), clamp(lon, count.lon))?;
+            total += weight;
+        }
+    }
+    Some(sum / total)
+}
+
+/// Catmull-Rom cubic convolution kernel, see [Interpolation::Bicubic]
+fn catmull_rom(x: f64) -> f64 {
+    let x = x.abs();
+    match x {
+        x if x <= 1.0 => (1.5 * x - 2.5) * x * x + 1.0,
+        x if x < 2.0 => ((-0.5 * x + 2.5) * x - 4.0) * x + 2.0,
+        _ => 0.0,
+    }
+}
+
+/// Lanczos kernel, see [Interpolation::Lanczos]
+fn lanczos(x: f64) -> f64 {
+    let a = LANCZOS_LOBES as f64;
+    let x = x.abs();
+    if x == 0.0 {
+        return 1.0;
+    }
+    match x < a {
+        true => {
+            let pi_x = std::f64::consts::PI * x;
+            a * pi_x.sin() * (pi_x / a).sin() / (pi_x * pi_x)
+        }
+        false => 0.0,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 /// DTED Data Set Identification (DSI) Record
 ///
 /// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
+///
+/// Blank fields within the record are represented as [Option::None].
+/// Dates are formatted as `YYMM`.
 pub struct DTEDRecordDSI {
+    /// Security Classification Code
+    pub security_code: Option<SecurityCode>,
     /// Security Control and Release Markings
     pub security_release: Option<String>,
     /// Security Handling Description
     pub security_handling: Option<String>,
-    pub version: String,
-    pub edition: u8,
-    pub mm_version: char,
-    pub maintenance_data: u16,
-    pub mm_date: u16,
-    pub maintenance_code: u16,
-    pub product_specs_desc: String,
-    pub product_specs_code: u8,
-    pub product_specs_date: u16,
-    pub compilation_date: u16,
-    pub lat_origin: Angle,
-    pub lon_origin: Angle,
-    pub lat_sw: Angle,
-    pub lon_sw: Angle,
-    pub lat_nw: Angle,
-    pub lon_nw: Angle,
-    pub lat_ne: Angle,
-    pub lon_ne: Angle,
-    pub lat_se: Angle,
-    pub lon_se: Angle,
-    pub clockwise_orientation: u32,
-    pub lat_interval_s: u16,
-    pub lon_interval_s: u16,
-    pub lat_count: u16,
-    pub lon_count: u16,
-    pub partial_cell_flag: f64,
-    pub coverage: f64,
+    /// Series designator for product level (e.g. `DTED2`)
+    pub version: Option<String>,
+    /// Unique reference number
+    pub reference: Option<String>,
+    /// Data edition number (01 - 99)
+    pub edition: Option<u8>,
+    /// Match / Merge version (A - Z)
+    pub mm_version: Option<char>,
+    /// Maintenance date (`YYMM`)
+    pub maintenance_date: Option<u16>,
+    /// Match / Merge date (`YYMM`)
+    pub mm_date: Option<u16>,
+    /// Maintenance description code
+    pub maintenance_code: Option<u16>,
+    /// Producer code (country / agency)
+    pub producer_code: Option<String>,
+    /// Product specification
+    pub product_specs_desc: Option<String>,
+    /// Product specification amendment / change number
+    pub product_specs_code: Option<u8>,
+    /// Date of product specification (`YYMM`)
+    pub product_specs_date: Option<u16>,
+    /// Vertical datum (e.g. `MSL`)
+    pub vertical_datum: Option<String>,
+    /// Horizontal datum (e.g. `WGS84`)
+    pub horizontal_datum: Option<String>,
+    /// Digitizing collection system
+    pub collection_system: Option<String>,
+    /// Compilation date (`YYMM`)
+    pub compilation_date: Option<u16>,
+    /// Latitude and longitude of the origin of the data
+    pub origin: AxisElement<Angle>,
+    /// Latitude and longitude of the south-west corner
+    pub sw_corner: AxisElement<Angle>,
+    /// Latitude and longitude of the north-west corner
+    pub nw_corner: AxisElement<Angle>,
+    /// Latitude and longitude of the north-east corner
+    pub ne_corner: AxisElement<Angle>,
+    /// Latitude and longitude of the south-east corner
+    pub se_corner: AxisElement<Angle>,
+    /// Clockwise orientation angle
+    pub clockwise_orientation: Angle,
+    /// Data interval in seconds (decimal point is implied after third integer)
+    pub interval_secs_x_10: AxisElement<u16>,
+    /// Number of latitude and longitude lines
+    pub count: AxisElement<u16>,
+    /// Partial cell indicator, 0 for complete coverage,
+    /// otherwise the percentage of data coverage (1 - 99)
+    pub partial_cell_flag: u8,
+}
+impl DTEDRecordDSI {
+    /// Create a [DTEDRecordDSI] describing the grid of a [DTEDMetadata], e.g.
+    /// for data which was not read from a file. Fields which cannot be derived
+    /// from the metadata are blank, apart from the (`MSL` / `WGS84`) datums and
+    /// the security classification, which is unclassified unless given by the UHL
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - [DTEDMetadata]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDRecordDSI]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::DTEDRecordDSI;
+    ///
+    /// let metadata = DTEDData::read_header("tests/test_data_negative.dt2").unwrap();
+    /// let dsi = DTEDRecordDSI::from_metadata(&metadata);
+    /// let read = metadata.dsi.unwrap();
+    /// assert_eq!(dsi.version, read.version);
+    /// assert_eq!(dsi.ne_corner, read.ne_corner);
+    /// assert_eq!(dsi.count, read.count);
+    /// ```
+    pub fn from_metadata(metadata: &DTEDMetadata) -> DTEDRecordDSI {
+        let origin = metadata.origin_angle;
+        let extent = (metadata.count - 1) * metadata.interval_secs;
+        let ne_corner = AxisElement::new(
+            Angle::from_secs(origin.lat.total_secs() + extent.lat as f64),
+            Angle::from_secs(origin.lon.total_secs() + extent.lon as f64),
+        );
+        DTEDRecordDSI {
+            security_code: Some(metadata.security_code.unwrap_or(SecurityCode::Unclassified)),
+            security_release: None,
+            security_handling: None,
+            version: metadata.level.map(|level| format!("DTED{}", level.value())),
+            reference: metadata.reference.clone(),
+            edition: None,
+            mm_version: None,
+            maintenance_date: None,
+            mm_date: None,
+            maintenance_code: None,
+            producer_code: None,
+            product_specs_desc: None,
+            product_specs_code: None,
+            product_specs_date: None,
+            vertical_datum: Some("MSL".to_string()),
+            horizontal_datum: Some("WGS84".to_string()),
+            collection_system: None,
+            compilation_date: None,
+            origin,
+            sw_corner: origin,
+            nw_corner: AxisElement::new(ne_corner.lat, origin.lon),
+            ne_corner,
+            se_corner: AxisElement::new(origin.lat, ne_corner.lon),
+            clockwise_orientation: Angle::from_secs(0.0),
+            interval_secs_x_10: AxisElement::new(
+                (metadata.interval_secs.lat * 10.0).round() as u16,
+                (metadata.interval_secs.lon * 10.0).round() as u16,
+            ),
+            count: metadata.count,
+            partial_cell_flag: 0,
+        }
+    }
+
+    /// Fraction of the cell covered by data, between 0 and 1
+    ///
+    /// # Returns
+    ///
+    /// * `1.0` if the cell is complete, otherwise the fraction of coverage
+    ///   given by [DTEDRecordDSI::partial_cell_flag]
+    pub fn coverage(&self) -> f64 {
+        match self.partial_cell_flag {
+            0 => 1.0,
+            pct => pct as f64 / 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+/// DTED accuracy values, in meters, with 90% assurance
+/// that the linear errors will not exceed these values.
+/// Values which are not available are [Option::None]
+///
+/// # Fields
+///
+/// * `abs_horizontal` - absolute horizontal accuracy
+/// * `abs_vertical` - absolute vertical accuracy
+/// * `rel_horizontal` - relative (point-to-point) horizontal accuracy
+/// * `rel_vertical` - relative (point-to-point) vertical accuracy
+pub struct DTEDAccuracy {
+    pub abs_horizontal: Option<u16>,
+    pub abs_vertical: Option<u16>,
+    pub rel_horizontal: Option<u16>,
+    pub rel_vertical: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// DTED accuracy sub-region, a polygon within the cell
+/// with its own [DTEDAccuracy]
+///
+/// # Fields
+///
+/// * `accuracy` - [DTEDAccuracy] within the sub-region
+/// * `outline` - latitude and longitude of the polygon vertices (3 - 14)
+pub struct DTEDAccuracySubRegion {
+    pub accuracy: DTEDAccuracy,
+    pub outline: Vec<AxisElement<Angle>>,
+}
+impl DTEDAccuracySubRegion {
+    /// Whether or not a lat/lon lies within the outline of the sub-region
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the point is inside the outline polygon
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::dted::{ DTEDAccuracy, DTEDAccuracySubRegion };
+    /// use dted2::primitives::{ Angle, AxisElement };
+    ///
+    /// let deg = |d: u16| Angle::new(d, 0, 0.0, false);
+    /// let region = DTEDAccuracySubRegion {
+    ///     accuracy: DTEDAccuracy::default(),
+    ///     outline: vec![
+    ///         AxisElement::new(deg(10), deg(20)),
+    ///         AxisElement::new(deg(11), deg(20)),
+    ///         AxisElement::new(deg(11), deg(21)),
+    ///         AxisElement::new(deg(10), deg(21)),
+    ///     ],
+    /// };
+    /// assert!(region.contains(10.5, 20.5));
+    /// assert!(!region.contains(11.5, 20.5));
+    /// ```
+    pub fn contains<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> bool {
+        let lat: f64 = lat.into();
+        let lon: f64 = lon.into();
+        let vertices: Vec<AxisElement<f64>> = self.outline.iter().map(|&v| v.into()).collect();
+        // --------------------------------------------------
+        // ray casting (even-odd rule)
+        // --------------------------------------------------
+        let mut inside = false;
+        let mut j = vertices.len().wrapping_sub(1);
+        for (i, vi) in vertices.iter().enumerate() {
+            let vj = vertices[j];
+            if (vi.lat > lat) != (vj.lat > lat)
+                && lon < (vj.lon - vi.lon) * (lat - vi.lat) / (vj.lat - vi.lat) + vi.lon
+            {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
 }
 
-/// TODO
-pub struct DTEDRecordACC {}
+#[derive(Debug, Clone, PartialEq)]
+/// DTED Accuracy Description (ACC) Record
+///
+/// See: [https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf](https://www.dlr.de/de/eoc/downloads/dokumente/7_sat_miss/SRTM-XSAR-DEM-DTED-1.1.pdf)
+///
+/// # Fields
+///
+/// * `accuracy` - [DTEDAccuracy] of the entire cell
+/// * `multiple_accuracy_flag` - multiple accuracy outline flag, 0 if there
+///   are no sub-regions, otherwise the number of sub-regions (2 - 9)
+/// * `sub_regions` - [DTEDAccuracySubRegion]s
+pub struct DTEDRecordACC {
+    pub accuracy: DTEDAccuracy,
+    pub multiple_accuracy_flag: u8,
+    pub sub_regions: Vec<DTEDAccuracySubRegion>,
+}
+impl DTEDRecordACC {
+    /// Get the accuracy at a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDAccuracy] of the first sub-region containing the point,
+    ///   otherwise the accuracy of the entire cell
+    pub fn accuracy_at<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> DTEDAccuracy {
+        let lat: f64 = lat.into();
+        let lon: f64 = lon.into();
+        self.sub_regions
+            .iter()
+            .find(|region| region.contains(lat, lon))
+            .map_or(self.accuracy, |region| region.accuracy)
+    }
+}
 
 pub struct RawDTEDFile {
     pub header: RawDTEDHeader,
     pub data: Vec<RawDTEDRecord>,
-    pub dsi_record: Option<u8>,
-    pub acc_record: Option<u8>,
+    pub dsi_record: Option<DTEDRecordDSI>,
+    pub acc_record: Option<DTEDRecordACC>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct RawDTEDRecord {
     pub blk_count: u32,
     pub lon_count: u16,
     pub lat_count: u16,
     pub elevations: Vec<i16>,
+    pub checksum: u32,
+}
+impl RawDTEDRecord {
+    /// Length of the record in bytes, including the sentinel and checksum
+    pub fn len(&self) -> usize {
+        DT2_RECORD_OVERHEAD_LENGTH + 2 * self.elevations.len()
+    }
+
+    /// Whether or not the record contains no elevations
+    pub fn is_empty(&self) -> bool {
+        self.elevations.is_empty()
+    }
+}
+
+/// Read and parse the UHL, DSI and ACC records from a [Read] source
+///
+/// # Arguments
+///
+/// * `reader` - [Read] source, positioned at the UHL. Exactly
+///   [DT2_HEADER_LENGTH] bytes are read, leaving it positioned
+///   at the first data record
+///
+/// # Returns
+///
+/// * [RawDTEDHeader], [DTEDRecordDSI], [DTEDRecordACC], and the raw bytes of the records
+fn header_records_from_reader<R: Read>(
+    reader: &mut R,
+) -> Result<(RawDTEDHeader, DTEDRecordDSI, DTEDRecordACC, Vec<u8>), DTEDError> {
+    let mut content = Vec::with_capacity(DT2_HEADER_LENGTH);
+    reader
+        .take(DT2_HEADER_LENGTH as u64)
+        .read_to_end(&mut content)?;
+    let parsed = tuple((
+        parsers::dted_uhl_parser,
+        parsers::dted_dsi_parser,
+        parsers::dted_acc_parser,
+    ))(&content);
+    match parsed {
+        Ok((_, (header, dsi, acc))) => {
+            verify_counts(&header, Some(&dsi))?;
+            Ok((header, dsi, acc, content))
+        }
+        Err(e) => Err(DTEDError::from_nom(&content, e)),
+    }
+}
+
+/// Verify the checksum of each data record against its raw bytes
+///
+/// # Arguments
+///
+/// * `content` - raw bytes of the data records
+/// * `record_len` - length of each data record, see [RawDTEDHeader::record_len]
+/// * `first_column` - column (longitude line) index of the first record within the file
+/// * `mode` - [ChecksumMode]
+///
+/// # Returns
+///
+/// * Indices of the records (within `content`) with invalid checksums, or an error if
+///   `mode` is [ChecksumMode::Fail] and any checksum is invalid
+pub(crate) fn verify_checksums(
+    content: &[u8],
+    record_len: usize,
+    first_column: usize,
+    mode: ChecksumMode,
+) -> Result<Vec<usize>, DTEDError> {
+    let mut invalid = Vec::new();
+    if mode == ChecksumMode::Ignore {
+        return Ok(invalid);
+    }
+    for (index, record) in content.chunks_exact(record_len).enumerate() {
+        let column = first_column + index;
+        // checksum is the last 4 bytes of the record
+        let (bytes, checksum) = record.split_at(record_len - 4);
+        let stored = u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+        let computed = parsers::dted_record_checksum(bytes);
+        if computed == stored {
+            continue;
+        }
+        match mode {
+            ChecksumMode::Fail => {
+                return Err(DTEDError::ChecksumMismatch {
+                    section: Section::Data(column),
+                    offset: DT2_HEADER_LENGTH + column * record_len + bytes.len(),
+                    stored,
+                    computed,
+                })
+            }
+            _ => invalid.push(index),
+        }
+    }
+    Ok(invalid)
+}
+
+/// Verify the number of latitude and longitude lines within the
+/// [DTEDRecordDSI] (if present) match the [RawDTEDHeader]
+///
+/// # Arguments
+///
+/// * `header` - [RawDTEDHeader]
+/// * `dsi` - [DTEDRecordDSI], if present
+///
+/// # Returns
+///
+/// * An error if the counts do not match
+pub(crate) fn verify_counts(header: &RawDTEDHeader, dsi: Option<&DTEDRecordDSI>) -> Result<(), DTEDError> {
+    let dsi = match dsi {
+        Some(dsi) => dsi,
+        None => return Ok(()),
+    };
+    // offsets of the latitude and longitude counts within the DSI record
+    let fields = [
+        (header.count.lat, dsi.count.lat, DT2_DSI_LAT_COUNT_OFFSET),
+        (header.count.lon, dsi.count.lon, DT2_DSI_LAT_COUNT_OFFSET + 4),
+    ];
+    for (expected, found, offset) in fields {
+        if expected != found {
+            return Err(DTEDError::CountMismatch {
+                section: Section::DSI,
+                offset: DT2_UHL_LENGTH as usize + offset,
+                expected: expected as usize,
+                found: found as usize,
+            });
+        }
+    }
+    Ok(())
 }
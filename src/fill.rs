@@ -0,0 +1,240 @@
+//! Filling void (no data) posts within DTED data.
+//!
+//! The main entry point is [`DTEDData::fill_voids`], using a [`FillMethod`].
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{Elevation, DT2_RECORD_OVERHEAD_LENGTH, DT2_VOID};
+use crate::writers;
+use crate::DTEDData;
+
+#[derive(Clone, Copy)]
+/// Method used to fill void posts, see [DTEDData::fill_voids]
+///
+/// Distances are measured in posts, i.e. as grid indices
+pub enum FillMethod<'a> {
+    /// Inverse distance weighting of the posts which are not void
+    /// within `radius` posts (along each axis) of the void post,
+    /// weighted by `1 / distance ^ power`
+    InverseDistance { radius: usize, power: f64 },
+    /// Iterative Laplacian (membrane) interpolation, which smoothly
+    /// interpolates across voids from the surrounding posts. Iteration
+    /// stops once no post changes by more than `tolerance` meters,
+    /// or after `max_iterations`
+    Laplacian { max_iterations: usize, tolerance: f64 },
+    /// Elevations interpolated from a secondary [DTEDData] covering the
+    /// same area, e.g. filling a level 2 tile from a level 1 tile
+    Secondary(&'a DTEDData),
+}
+
+impl DTEDData {
+    /// Fill the void posts ([DT2_VOID]) of the data
+    ///
+    /// Posts which cannot be filled (e.g. with no posts within the radius,
+    /// or outside of the secondary data) remain void. The checksums
+    /// of the records containing filled posts are recomputed
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - [FillMethod]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData] with the voids filled, and a mask indexed as
+    ///   `[lon_index][lat_index]` (see [DTEDData::void_mask]) where
+    ///   `true` marks a post synthesized by the fill
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::dted::DT2_VOID;
+    /// use dted2::fill::FillMethod;
+    ///
+    /// let mut data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// data.data[10].elevations[20] = DT2_VOID;
+    /// let method = FillMethod::InverseDistance { radius: 2, power: 2.0 };
+    /// let (filled, synthesized) = data.fill_voids(&method);
+    /// assert_eq!(filled.void_count(), 0);
+    /// assert!(synthesized[10][20]);
+    /// assert_eq!(synthesized.iter().flatten().filter(|&&s| s).count(), 1);
+    /// ```
+    pub fn fill_voids(&self, method: &FillMethod) -> (DTEDData, Vec<Vec<bool>>) {
+        let fills = match *method {
+            FillMethod::InverseDistance { radius, power } => self.inverse_distance(radius, power),
+            FillMethod::Laplacian { max_iterations, tolerance } => {
+                self.laplacian(max_iterations, tolerance)
+            }
+            FillMethod::Secondary(secondary) => self.secondary(secondary),
+        };
+        // --------------------------------------------------
+        // apply the fills
+        // --------------------------------------------------
+        let mut filled = self.clone();
+        let mut synthesized: Vec<Vec<bool>> = self
+            .data
+            .iter()
+            .map(|record| vec![false; record.elevations.len()])
+            .collect();
+        for (lon, lat, value) in fills {
+            filled.data[lon].elevations[lat] = to_post(value);
+            synthesized[lon][lat] = true;
+        }
+        // --------------------------------------------------
+        // recompute the checksums of the modified records
+        // --------------------------------------------------
+        let lat_count = self.metadata.count.lat as usize;
+        let mut bytes = Vec::with_capacity(DT2_RECORD_OVERHEAD_LENGTH + 2 * lat_count);
+        for (record, synthesized) in filled.data.iter_mut().zip(synthesized.iter()) {
+            if !synthesized.contains(&true) {
+                continue;
+            }
+            writers::dted_record_bytes(record.blk_count as usize, &record.elevations, &mut bytes);
+            let checksum = &bytes[bytes.len() - 4..];
+            record.checksum = u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+        }
+        (filled, synthesized)
+    }
+
+    /// Indices `(lon_index, lat_index)` of every void post
+    fn voids(&self) -> Vec<(usize, usize)> {
+        self.data
+            .iter()
+            .enumerate()
+            .flat_map(|(lon, record)| {
+                record
+                    .elevations
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &elev)| elev == DT2_VOID)
+                    .map(move |(lat, _)| (lon, lat))
+            })
+            .collect()
+    }
+
+    /// Elevation of a post, None if void or out of bounds
+    fn post_value(&self, lon: isize, lat: isize) -> Option<f64> {
+        if lon < 0 || lat < 0 {
+            return None;
+        }
+        let elev = *self.data.get(lon as usize)?.elevations.get(lat as usize)?;
+        Elevation::from_post(elev).value()
+    }
+
+    /// Fill the voids using inverse distance weighting, see [FillMethod::InverseDistance]
+    fn inverse_distance(&self, radius: usize, power: f64) -> Vec<(usize, usize, f64)> {
+        let radius = radius as isize;
+        self.voids()
+            .into_iter()
+            .filter_map(|(lon, lat)| {
+                let (mut sum, mut weight) = (0.0, 0.0);
+                for d_lon in -radius..=radius {
+                    for d_lat in -radius..=radius {
+                        let value = self.post_value(lon as isize + d_lon, lat as isize + d_lat);
+                        if let Some(value) = value {
+                            let distance = ((d_lon * d_lon + d_lat * d_lat) as f64).sqrt();
+                            let w = 1.0 / distance.powf(power);
+                            sum += w * value;
+                            weight += w;
+                        }
+                    }
+                }
+                match weight > 0.0 {
+                    true => Some((lon, lat, sum / weight)),
+                    false => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Fill the voids using iterative Laplacian interpolation, see [FillMethod::Laplacian]
+    fn laplacian(&self, max_iterations: usize, tolerance: f64) -> Vec<(usize, usize, f64)> {
+        let voids = self.voids();
+        // --------------------------------------------------
+        // start from the mean of the posts which are not void
+        // --------------------------------------------------
+        let (sum, count) = self
+            .data
+            .iter()
+            .flat_map(|record| record.elevations.iter())
+            .filter_map(|&elev| Elevation::from_post(elev).value())
+            .fold((0.0, 0_usize), |(sum, count), elev| (sum + elev, count + 1));
+        if count == 0 {
+            return Vec::new();
+        }
+        let mut grid: Vec<Vec<f64>> = self
+            .data
+            .iter()
+            .map(|record| {
+                record
+                    .elevations
+                    .iter()
+                    .map(|&elev| Elevation::from_post(elev).value().unwrap_or(sum / count as f64))
+                    .collect()
+            })
+            .collect();
+        // --------------------------------------------------
+        // Jacobi iterations, where each void post becomes the mean of
+        // its (up to 4) neighbouring posts in the previous iteration.
+        // Posts which are not void are the same in both grids
+        // --------------------------------------------------
+        let mut next = grid.clone();
+        for _ in 0..max_iterations {
+            let mut max_change: f64 = 0.0;
+            for &(lon, lat) in voids.iter() {
+                let (mut total, mut neighbours) = (0.0, 0);
+                if lon > 0 {
+                    total += grid[lon - 1][lat];
+                    neighbours += 1;
+                }
+                if lon + 1 < grid.len() {
+                    total += grid[lon + 1][lat];
+                    neighbours += 1;
+                }
+                if lat > 0 {
+                    total += grid[lon][lat - 1];
+                    neighbours += 1;
+                }
+                if lat + 1 < grid[lon].len() {
+                    total += grid[lon][lat + 1];
+                    neighbours += 1;
+                }
+                if neighbours == 0 {
+                    continue;
+                }
+                let value = total / neighbours as f64;
+                max_change = max_change.max((value - grid[lon][lat]).abs());
+                next[lon][lat] = value;
+            }
+            std::mem::swap(&mut grid, &mut next);
+            if max_change <= tolerance {
+                break;
+            }
+        }
+        voids
+            .into_iter()
+            .map(|(lon, lat)| (lon, lat, grid[lon][lat]))
+            .collect()
+    }
+
+    /// Fill the voids from a secondary [DTEDData], see [FillMethod::Secondary]
+    fn secondary(&self, secondary: &DTEDData) -> Vec<(usize, usize, f64)> {
+        let interval = self.metadata.interval;
+        self.voids()
+            .into_iter()
+            .filter_map(|(lon, lat)| {
+                let value = secondary.get_elevation(
+                    self.min.lat + lat as f64 * interval.lat,
+                    self.min.lon + lon as f64 * interval.lon,
+                )?;
+                Some((lon, lat, value))
+            })
+            .collect()
+    }
+}
+
+/// Round an elevation to a post, which is never [DT2_VOID]
+fn to_post(value: f64) -> i16 {
+    value.round().clamp(DT2_VOID as f64 + 1.0, i16::MAX as f64) as i16
+}
@@ -0,0 +1,359 @@
+//! Index of the DTED files within a directory tree.
+//!
+//! The main entry point is [`DTEDIndex`].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use thiserror::Error as ThisError;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{self, DTEDLevel, LevelWarning};
+use crate::mosaic::DTEDMosaic;
+use crate::primitives::AxisElement;
+use crate::{DTEDData, DTEDMetadata};
+use crate::Error as DTEDError;
+
+/// Tolerance (in degrees) for the origin of a tile lying on a cell edge
+const EPS: f64 = 1e-6;
+
+#[derive(Clone)]
+/// DTED file within a [DTEDIndex]
+///
+/// # Fields
+///
+/// * `path` - path to the DTED file
+/// * `cell` - latitude and longitude (in integer degrees) of the cell, from the header
+/// * `level` - [DTEDLevel] of the file
+/// * `metadata` - [DTEDMetadata] read from the header records
+pub struct DTEDTile {
+    pub path: PathBuf,
+    pub cell: AxisElement<i16>,
+    pub level: DTEDLevel,
+    pub metadata: DTEDMetadata,
+}
+impl DTEDTile {
+    /// Whether or not a lat/lon lies within the posts of the tile
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    pub fn contains<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> bool {
+        let min = self.metadata.origin;
+        let max = min + ((self.metadata.count - 1) * self.metadata.interval);
+        dted::grid_indices(min, max, self.metadata.interval, lat.into(), lon.into()).is_some()
+    }
+
+    /// Read the entire DTED file, see [DTEDData::read]
+    pub fn read(&self) -> Result<DTEDData, DTEDError> {
+        DTEDData::read(&self.path)
+    }
+}
+
+#[derive(Debug, ThisError)]
+/// Problem found while building a [DTEDIndex], see [DTEDIndex::warnings]
+pub enum IndexWarning {
+    /// The header records of a file could not be read. The file is not indexed
+    #[error("Unreadable DTED file {}: {error}", .path.display())]
+    Unreadable {
+        path: PathBuf,
+        #[source]
+        error: DTEDError,
+    },
+    /// The level of a file is unknown (see [LevelWarning::Unknown]).
+    /// The file is not indexed
+    #[error("Unknown DTED level of {}", .path.display())]
+    UnknownLevel { path: PathBuf },
+    /// More than one file covers the same cell at the same level. The
+    /// first file (in path order) is indexed, and the duplicate is not
+    #[error("Duplicate {level:?} tile for cell {cell:?}: {} is indexed, {} is not",
+        .indexed.display(), .duplicate.display())]
+    Duplicate {
+        cell: AxisElement<i16>,
+        level: DTEDLevel,
+        indexed: PathBuf,
+        duplicate: PathBuf,
+    },
+    /// The cell named by the path (see [cell_from_path]) disagrees with
+    /// the origin of the header. The file is indexed by its header
+    #[error("{} names cell {named:?}, but its origin is within cell {header:?}", .path.display())]
+    CellMismatch {
+        path: PathBuf,
+        named: AxisElement<i16>,
+        header: AxisElement<i16>,
+    },
+    /// The level of a file is inconsistent, see [DTEDMetadata::level_warnings]
+    #[error("{}: {warning}", .path.display())]
+    Level { path: PathBuf, warning: LevelWarning },
+}
+
+#[derive(Default)]
+/// Index of the DTED files within a directory tree, by cell and level
+///
+/// Only the header records of each file are read. Files are found
+/// by their extension (see [DTEDLevel::from_path]), within either the
+/// standard layout of a directory per longitude (e.g. `w178/s30.dt2`)
+/// or flat naming (e.g. `s30_w178.dt2`)
+///
+/// # Fields
+///
+/// * `warnings` - duplicates and conflicts found while building the
+///   index, see [IndexWarning]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DTEDLevel;
+/// use dted2::index::DTEDIndex;
+///
+/// let root = std::env::temp_dir().join("dted2_doc_index");
+/// std::fs::create_dir_all(root.join("w178")).unwrap();
+/// std::fs::copy("tests/test_data_negative.dt2", root.join("w178").join("s30.dt0")).unwrap();
+///
+/// let index = DTEDIndex::scan(&root).unwrap();
+/// assert!(index.warnings.is_empty());
+/// let tile = index.best(-29.5, -177.5).unwrap();
+/// assert_eq!(tile.level, DTEDLevel::Level0);
+/// assert!(index.best(-28.5, -177.5).is_none());
+/// assert!(tile.read().unwrap().get_elevation(-29.5, -177.5).is_some());
+/// ```
+pub struct DTEDIndex {
+    tiles: HashMap<AxisElement<i16>, BTreeMap<DTEDLevel, DTEDTile>>,
+    pub warnings: Vec<IndexWarning>,
+}
+impl DTEDIndex {
+    /// Create an empty [DTEDIndex]
+    pub fn new() -> DTEDIndex {
+        DTEDIndex::default()
+    }
+
+    /// Build a [DTEDIndex] from the DTED files within a directory tree
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - root directory, which is scanned recursively
+    ///   (without following symbolic links to directories)
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDIndex], or an error if a directory cannot be read. Files
+    ///   which cannot be read are reported in [DTEDIndex::warnings]
+    pub fn scan<P: AsRef<Path>>(root: P) -> Result<DTEDIndex, DTEDError> {
+        let mut paths = Vec::new();
+        find_files(root.as_ref(), &mut paths)?;
+        paths.sort();
+        let mut index = DTEDIndex::new();
+        for path in paths {
+            match DTEDData::read_header(&path) {
+                Ok(metadata) => index.insert(path, metadata),
+                Err(error) => index.warnings.push(IndexWarning::Unreadable { path, error }),
+            }
+        }
+        Ok(index)
+    }
+
+    /// Add a DTED file to the index
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - path to the DTED file
+    /// * `metadata` - [DTEDMetadata] read from the header records of the file
+    pub fn insert(&mut self, path: PathBuf, metadata: DTEDMetadata) {
+        let cell = DTEDMosaic::cell(metadata.origin.lat + EPS, metadata.origin.lon + EPS);
+        // --------------------------------------------------
+        // conflicts between the path and the header
+        // --------------------------------------------------
+        if let Some(named) = cell_from_path(&path) {
+            if named != cell {
+                self.warnings.push(IndexWarning::CellMismatch {
+                    path: path.clone(),
+                    named,
+                    header: cell,
+                });
+            }
+        }
+        let level = match metadata.level {
+            Some(level) => level,
+            None => {
+                self.warnings.push(IndexWarning::UnknownLevel { path });
+                return;
+            }
+        };
+        for warning in metadata.level_warnings() {
+            self.warnings.push(IndexWarning::Level {
+                path: path.clone(),
+                warning,
+            });
+        }
+        // --------------------------------------------------
+        // duplicates of the cell and level
+        // --------------------------------------------------
+        let levels = self.tiles.entry(cell).or_default();
+        match levels.get(&level) {
+            Some(indexed) => self.warnings.push(IndexWarning::Duplicate {
+                cell,
+                level,
+                indexed: indexed.path.clone(),
+                duplicate: path,
+            }),
+            None => {
+                levels.insert(level, DTEDTile { path, cell, level, metadata });
+            }
+        }
+    }
+
+    /// Get the file covering a cell at a level
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - latitude and longitude (in integer degrees) of the cell
+    /// * `level` - [DTEDLevel]
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDTile], or None if there is no file for the cell and level
+    pub fn get(&self, cell: AxisElement<i16>, level: DTEDLevel) -> Option<&DTEDTile> {
+        self.tiles.get(&cell)?.get(&level)
+    }
+
+    /// Iterate over the files covering a cell, from the lowest to the highest level
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - latitude and longitude (in integer degrees) of the cell
+    pub fn levels(&self, cell: AxisElement<i16>) -> impl Iterator<Item = &DTEDTile> {
+        self.tiles.get(&cell).into_iter().flat_map(BTreeMap::values)
+    }
+
+    /// Iterate over every indexed file, in arbitrary order
+    pub fn tiles(&self) -> impl Iterator<Item = &DTEDTile> {
+        self.tiles.values().flat_map(BTreeMap::values)
+    }
+
+    /// Number of indexed files
+    pub fn len(&self) -> usize {
+        self.tiles.values().map(BTreeMap::len).sum()
+    }
+
+    /// Whether or not any files are indexed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the file with the best (highest) level containing a lat/lon
+    ///
+    /// Points on the edge of a cell are contained by the files on either
+    /// side. Between files of the same level, the file of the cell
+    /// containing the point (see [DTEDMosaic::cell]) is preferred
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDTile], or None if no file contains the point
+    pub fn best<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<&DTEDTile> {
+        let lat: f64 = lat.into();
+        let lon: f64 = lon.into();
+        let cell = DTEDMosaic::cell(lat, lon);
+        let offsets = [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)];
+        offsets
+            .into_iter()
+            .flat_map(|(d_lat, d_lon)| {
+                self.levels(AxisElement::new(cell.lat + d_lat, cell.lon + d_lon))
+            })
+            .filter(|tile| tile.contains(lat, lon))
+            .reduce(|best, tile| match tile.level > best.level {
+                true => tile,
+                false => best,
+            })
+    }
+}
+
+/// Get the cell named by the path of a DTED file
+///
+/// The latitude is named by the file stem (e.g. `s30`), and the longitude by
+/// either the file stem (e.g. `s30_w178` or `s30w178`) or the parent
+/// directory, as in the standard layout (e.g. `w178/s30.dt2`)
+///
+/// # Arguments
+///
+/// * `path` - path to a DTED file
+///
+/// # Returns
+///
+/// * Latitude and longitude (in integer degrees) of the
+///   cell, or None if the path does not name a cell
+///
+/// # Examples
+///
+/// ```
+/// use dted2::index::cell_from_path;
+/// use dted2::primitives::AxisElement;
+///
+/// assert_eq!(cell_from_path("dted/w178/s30.dt2"), Some(AxisElement::new(-30, -178)));
+/// assert_eq!(cell_from_path("E010/N50.DT1"), Some(AxisElement::new(50, 10)));
+/// assert_eq!(cell_from_path("tiles/n50_e010.dt0"), Some(AxisElement::new(50, 10)));
+/// assert_eq!(cell_from_path("tiles/n50.dt0"), None);
+/// ```
+pub fn cell_from_path<P: AsRef<Path>>(path: P) -> Option<AxisElement<i16>> {
+    let path = path.as_ref();
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    let lat = named_degrees(&stem, ['n', 's'], 90)?;
+    let lon = match named_degrees(&stem, ['e', 'w'], 180) {
+        Some(lon) => lon,
+        None => {
+            let parent = path.parent()?.file_name()?.to_str()?.to_ascii_lowercase();
+            named_degrees(&parent, ['e', 'w'], 180)?
+        }
+    };
+    Some(AxisElement::new(lat, lon))
+}
+
+/// Degrees named by a hemisphere followed by digits (e.g. `w178`)
+///
+/// # Arguments
+///
+/// * `name` - lowercase file or directory name
+/// * `hemispheres` - (positive, negative) hemispheres
+/// * `max` - maximum number of degrees
+fn named_degrees(name: &str, hemispheres: [char; 2], max: i16) -> Option<i16> {
+    name.char_indices().find_map(|(i, c)| {
+        let sign = match c {
+            c if c == hemispheres[0] => 1,
+            c if c == hemispheres[1] => -1,
+            _ => return None,
+        };
+        let digits: String = name[i + 1..].chars().take_while(char::is_ascii_digit).collect();
+        match digits.parse::<i16>() {
+            Ok(deg) if digits.len() <= 3 && deg <= max => Some(sign * deg),
+            _ => None,
+        }
+    })
+}
+
+/// Recursively find the files with a DTED extension within a directory
+///
+/// Symbolic links to directories are not followed, as they may form
+/// loops, while symbolic links to files are found as any other file
+fn find_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), DTEDError> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let file_type = std::fs::symlink_metadata(&path)?.file_type();
+        if file_type.is_dir() {
+            find_files(&path, paths)?;
+        } else if file_type.is_symlink() && path.is_dir() {
+            continue;
+        } else if DTEDLevel::from_path(&path).is_some() {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
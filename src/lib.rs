@@ -3,37 +3,251 @@
 // --------------------------------------------------
 // external
 // --------------------------------------------------
+use std::fmt;
 use std::io;
+use thiserror::Error as ThisError;
 
 // --------------------------------------------------
 // local
 // --------------------------------------------------
+pub mod builder;
+pub mod cache;
 pub mod dted;
+pub mod fill;
+pub mod index;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+pub mod mosaic;
 pub mod parsers;
 pub mod primitives;
+pub mod terrain;
+pub mod writers;
 pub use dted::{ DTEDData, DTEDMetadata };
+use dted::{ DT2_DSI_RECORD_LENGTH, DT2_HEADER_LENGTH, DT2_RECORD_OVERHEAD_LENGTH, DT2_UHL_LENGTH };
 
-#[derive(Debug)]
-/// DTED parsing error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Section of a DTED file
 ///
-/// * Io - IO error
-/// * ParseError - parsing error
-pub enum Error {
-    Io(io::Error),
-    ParseError(String),
+/// * UHL - User Header Label
+/// * DSI - Data Set Identification record
+/// * ACC - Accuracy Description record
+/// * Data - data record, containing the column (longitude line) index
+pub enum Section {
+    UHL,
+    DSI,
+    ACC,
+    Data(usize),
 }
-impl From<io::Error> for Error {
-    fn from(err: io::Error) -> Error {
-        Error::Io(err)
+impl Section {
+    /// Get the [Section] containing a byte offset within a DTED file
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - byte offset from the start of the file
+    /// * `lat_count` - number of latitude points per longitude line,
+    ///   used to determine the column of a data record. If None, every
+    ///   offset past the header records is reported as column 0
+    ///
+    /// # Returns
+    ///
+    /// * [Section] containing the offset
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::Section;
+    /// assert_eq!(Section::from_offset(10, None), Section::UHL);
+    /// assert_eq!(Section::from_offset(100, None), Section::DSI);
+    /// assert_eq!(Section::from_offset(1000, None), Section::ACC);
+    /// assert_eq!(Section::from_offset(3428 + 254 * 2 + 3, None), Section::Data(0));
+    /// assert_eq!(Section::from_offset(3428 + 254 * 2 + 3, Some(121)), Section::Data(2));
+    /// ```
+    pub fn from_offset(offset: usize, lat_count: Option<u16>) -> Section {
+        let uhl_end = DT2_UHL_LENGTH as usize;
+        let dsi_end = uhl_end + DT2_DSI_RECORD_LENGTH;
+        match offset {
+            o if o < uhl_end => Section::UHL,
+            o if o < dsi_end => Section::DSI,
+            o if o < DT2_HEADER_LENGTH => Section::ACC,
+            o => match lat_count {
+                Some(lat_count) => {
+                    let record_len = DT2_RECORD_OVERHEAD_LENGTH + 2 * lat_count as usize;
+                    Section::Data((o - DT2_HEADER_LENGTH) / record_len)
+                }
+                None => Section::Data(0),
+            },
+        }
+    }
+
+    /// Byte offset from the start of the file where the [Section] begins
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_count` - number of latitude points per longitude line
+    ///
+    /// # Returns
+    ///
+    /// * Byte offset of the start of the section
+    pub fn start(&self, lat_count: u16) -> usize {
+        match self {
+            Section::UHL => 0,
+            Section::DSI => DT2_UHL_LENGTH as usize,
+            Section::ACC => DT2_UHL_LENGTH as usize + DT2_DSI_RECORD_LENGTH,
+            Section::Data(column) => {
+                DT2_HEADER_LENGTH + column * (DT2_RECORD_OVERHEAD_LENGTH + 2 * lat_count as usize)
+            }
+        }
     }
 }
-impl From<nom::Needed> for Error {
-    fn from(err: nom::Needed) -> Error {
-        Error::ParseError(format!("More data needed: {:?}", err))
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Section::UHL => write!(f, "UHL"),
+            Section::DSI => write!(f, "DSI record"),
+            Section::ACC => write!(f, "ACC record"),
+            Section::Data(column) => write!(f, "data record (column {})", column),
+        }
     }
 }
-impl From<nom::error::ErrorKind> for Error {
-    fn from(err: nom::error::ErrorKind) -> Error {
-        Error::ParseError(format!("Parsing error: {:?}", err))
+
+#[derive(Debug, ThisError)]
+/// DTED parsing error
+///
+/// Apart from [Error::Io], [Error::EmptyWindow] and [Error::DimensionMismatch],
+/// each error contains the [Section] and the byte offset (from the start
+/// of the file) where the error occurred
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Bad recognition sentinel for {section} at byte {offset}")]
+    BadSentinel { section: Section, offset: usize },
+    #[error("Truncated file, {section} ends unexpectedly at byte {offset}")]
+    Truncated { section: Section, offset: usize },
+    #[error("Bad numeric field in {section} at byte {offset}")]
+    BadNumericField { section: Section, offset: usize },
+    #[error("Count mismatch in {section} at byte {offset}: expected {expected}, found {found}")]
+    CountMismatch {
+        section: Section,
+        offset: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Checksum failure in {section} at byte {offset}: stored {stored}, computed {computed}")]
+    ChecksumMismatch {
+        section: Section,
+        offset: usize,
+        stored: u32,
+        computed: u32,
+    },
+    #[error("Window does not intersect the data")]
+    EmptyWindow,
+    #[error("Dimension mismatch: expected {expected} {axis}, found {found}")]
+    DimensionMismatch {
+        axis: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Parsing error ({kind:?}) in {section} at byte {offset}")]
+    Parse {
+        section: Section,
+        offset: usize,
+        kind: nom::error::ErrorKind,
+    },
+}
+impl Error {
+    /// Create an [Error] from a [nom] error, raised while parsing `content`
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the entire input being parsed, starting at the UHL
+    /// * `err` - [nom] error, which references a suffix of `content`
+    ///
+    /// # Returns
+    ///
+    /// * [Error], with the [Section] and offset of the error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::{ Error, Section };
+    /// use dted2::parsers::dted_uhl_parser;
+    ///
+    /// let content = b"UHL11780000W0300000S03000300NA  U";
+    /// let err = Error::from_nom(content, dted_uhl_parser(content).unwrap_err());
+    /// assert!(matches!(err, Error::Truncated { section: Section::UHL, .. }));
+    /// ```
+    pub fn from_nom(content: &[u8], err: nom::Err<nom::error::Error<&[u8]>>) -> Error {
+        let lat_count = parsers::dted_uhl_parser(content).ok().map(|(_, h)| h.count.lat);
+        Error::from_nom_at(content, 0, lat_count, err)
+    }
+
+    /// Create an [Error] from a [nom] error, raised while parsing `content`,
+    /// which starts at byte `start` of the file
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the input being parsed
+    /// * `start` - byte offset of `content` from the start of the file
+    /// * `lat_count` - number of latitude points per longitude line, if known
+    /// * `err` - [nom] error, which references a suffix of `content`
+    ///
+    /// # Returns
+    ///
+    /// * [Error], with the [Section] and offset of the error
+    pub(crate) fn from_nom_at(
+        content: &[u8],
+        start: usize,
+        lat_count: Option<u16>,
+        err: nom::Err<nom::error::Error<&[u8]>>,
+    ) -> Error {
+        let (offset, kind) = match err {
+            nom::Err::Incomplete(_) => (content.len(), nom::error::ErrorKind::Eof),
+            nom::Err::Error(e) | nom::Err::Failure(e) => (content.len() - e.input.len(), e.code),
+        };
+        let offset = start + offset;
+        let section = Section::from_offset(offset, lat_count);
+        match kind {
+            nom::error::ErrorKind::Tag if offset == section.start(lat_count.unwrap_or(0)) => {
+                Error::BadSentinel { section, offset }
+            }
+            nom::error::ErrorKind::Eof => Error::Truncated { section, offset },
+            // remaining tags are the decimal points of numeric fields
+            nom::error::ErrorKind::Tag
+            | nom::error::ErrorKind::Digit
+            | nom::error::ErrorKind::MapRes => Error::BadNumericField { section, offset },
+            kind => Error::Parse {
+                section,
+                offset,
+                kind,
+            },
+        }
+    }
+
+    /// [Section] where the error occurred, None for errors
+    /// which do not occur within a file (e.g. [Error::Io])
+    pub fn section(&self) -> Option<Section> {
+        match self {
+            Error::Io(_) | Error::EmptyWindow | Error::DimensionMismatch { .. } => None,
+            Error::BadSentinel { section, .. }
+            | Error::Truncated { section, .. }
+            | Error::BadNumericField { section, .. }
+            | Error::CountMismatch { section, .. }
+            | Error::ChecksumMismatch { section, .. }
+            | Error::Parse { section, .. } => Some(*section),
+        }
+    }
+
+    /// Byte offset (from the start of the file) where the error
+    /// occurred, None for errors which do not occur within a file (e.g. [Error::Io])
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Error::Io(_) | Error::EmptyWindow | Error::DimensionMismatch { .. } => None,
+            Error::BadSentinel { offset, .. }
+            | Error::Truncated { offset, .. }
+            | Error::BadNumericField { offset, .. }
+            | Error::CountMismatch { offset, .. }
+            | Error::ChecksumMismatch { offset, .. }
+            | Error::Parse { offset, .. } => Some(*offset),
+        }
     }
 }
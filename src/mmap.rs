@@ -0,0 +1,208 @@
+//! Memory-mapped, zero-copy access to DTED files.
+//!
+//! Requires the `mmap` feature. The main entry point is [`DTEDMmap`].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use memmap2::Mmap;
+use std::path::Path;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{
+    self, ChecksumMode, DTEDMetadata, DTEDReadOptions, Elevation, DT2_HEADER_LENGTH,
+    DT2_RECORD_OVERHEAD_LENGTH,
+};
+use crate::parsers;
+use crate::primitives::AxisElement;
+use crate::DTEDData;
+use crate::Error as DTEDError;
+use crate::Section;
+
+/// Offset of the first elevation within a data record
+/// (sentinel, block count, longitude count, and latitude count)
+const DT2_RECORD_ELEVATION_OFFSET: usize = DT2_RECORD_OVERHEAD_LENGTH - 4;
+
+/// Memory-mapped DTED Data
+///
+/// A [DTEDData]-like view of a DTED file, which maps the file into memory
+/// rather than reading it. Elevations are decoded on demand from the bytes of
+/// each data record, so no copy of the elevations is ever allocated.
+///
+/// The file must not be modified (or truncated) by another process while it
+/// is mapped, see [memmap2::Mmap].
+///
+/// # Fields
+///
+/// * `metadata` - [DTEDMetadata]
+/// * `min` - minimum lat/lon
+/// * `max` - maximum lat/lon
+/// * `invalid_checksums` - indices of the data records (columns) with
+///   invalid checksums, see [ChecksumMode::Warn]. Empty unless verified,
+///   see [DTEDMmap::open_with_options]
+pub struct DTEDMmap {
+    pub metadata: DTEDMetadata,
+    pub min: AxisElement<f64>,
+    pub max: AxisElement<f64>,
+    pub invalid_checksums: Vec<usize>,
+    record_len: usize,
+    mmap: Mmap,
+}
+impl DTEDMmap {
+    /// Memory-map a DTED file
+    ///
+    /// Only the header records are parsed, and checksums are not verified
+    /// ([ChecksumMode::Ignore]), so the data records are only paged in as they
+    /// are queried. To verify the checksums, which reads every data record once,
+    /// use [DTEDMmap::open_with_options]
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMmap]: memory-mapped DTED data
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::mmap::DTEDMmap;
+    /// let dted_data = DTEDMmap::open("tests/test_data_negative.dt2").unwrap();
+    /// assert!(dted_data.get_elevation(-29.5, -177.5).is_some());
+    /// assert!(dted_data.get_elevation(0.0, 0.0).is_none());
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<DTEDMmap, DTEDError> {
+        let options = DTEDReadOptions {
+            checksum: ChecksumMode::Ignore,
+            ..Default::default()
+        };
+        Self::open_with_options(path, &options)
+    }
+
+    /// Memory-map a DTED file using [DTEDReadOptions]
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: Path to the DTED file
+    /// * `options` - [DTEDReadOptions]. [DTEDReadOptions::window] is ignored, as
+    ///   posts outside of any window are never decoded
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMmap]: memory-mapped DTED data
+    pub fn open_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &DTEDReadOptions,
+    ) -> Result<DTEDMmap, DTEDError> {
+        let file = std::fs::File::open(path.as_ref())?;
+        // SAFETY: the file is opened read-only, and the mapping is never
+        // handed out mutably. As documented on [DTEDMmap], the caller must
+        // ensure the file is not modified while it is mapped
+        let mmap = unsafe { Mmap::map(&file)? };
+        // --------------------------------------------------
+        // parse the header records
+        // --------------------------------------------------
+        let mut metadata = DTEDData::header_from_bytes(&mmap[..mmap.len().min(DT2_HEADER_LENGTH)])?;
+        metadata.set_filename(path.as_ref());
+        let record_len = DT2_RECORD_OVERHEAD_LENGTH + 2 * metadata.count.lat as usize;
+        let file_len = DT2_HEADER_LENGTH + metadata.count.lon as usize * record_len;
+        if mmap.len() < file_len {
+            return Err(DTEDError::Truncated {
+                section: Section::from_offset(mmap.len(), Some(metadata.count.lat)),
+                offset: mmap.len(),
+            });
+        }
+        // --------------------------------------------------
+        // verify the data records
+        // --------------------------------------------------
+        let invalid_checksums = dted::verify_checksums(
+            &mmap[DT2_HEADER_LENGTH..file_len],
+            record_len,
+            0,
+            options.checksum,
+        )?;
+        let origin = metadata.origin;
+        let max = origin + ((metadata.count - 1) * metadata.interval);
+        Ok(DTEDMmap {
+            metadata,
+            min: origin,
+            max,
+            invalid_checksums,
+            record_len,
+            mmap,
+        })
+    }
+
+    /// Get the elevation of a single post, decoded from the mapped bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `lat_idx` - latitude index (row)
+    /// * `lon_idx` - longitude index (column)
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters) or None if out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::mmap::DTEDMmap;
+    /// let mapped = DTEDMmap::open("tests/test_data_negative.dt2").unwrap();
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(mapped.get_post(60, 30), Some(data.data[30].elevations[60]));
+    /// assert_eq!(mapped.get_post(121, 0), None);
+    /// ```
+    pub fn get_post(&self, lat_idx: usize, lon_idx: usize) -> Option<i16> {
+        if lat_idx >= self.metadata.count.lat as usize || lon_idx >= self.metadata.count.lon as usize {
+            return None;
+        }
+        Some(self.post(lat_idx, lon_idx))
+    }
+
+    /// Get the elevation at a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters), or None if out of bounds or void,
+    ///   see [crate::DTEDData::get_elevation]
+    pub fn get_elevation<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+        let indices = self.get_indices(lat, lon)?;
+        dted::bilinear(self.metadata.count, indices, |lat_int, lon_int| {
+            Elevation::from_post(self.post(lat_int, lon_int)).value()
+        })
+    }
+
+    /// Get the indices of a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * `(lat_index, lon_index)` or None if out of bounds
+    pub fn get_indices<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<(f64, f64)> {
+        dted::grid_indices(self.min, self.max, self.metadata.interval, lat.into(), lon.into())
+    }
+
+    /// Decode the post at `(lat_idx, lon_idx)`, which must be within bounds
+    #[inline]
+    fn post(&self, lat_idx: usize, lon_idx: usize) -> i16 {
+        let offset = DT2_HEADER_LENGTH
+            + lon_idx * self.record_len
+            + DT2_RECORD_ELEVATION_OFFSET
+            + 2 * lat_idx;
+        parsers::to_i16(u16::from_be_bytes([self.mmap[offset], self.mmap[offset + 1]]))
+    }
+}
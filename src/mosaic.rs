@@ -0,0 +1,272 @@
+//! Seamless queries across many DTED tiles.
+//!
+//! The main entry point is [`DTEDMosaic`].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::collections::HashMap;
+use std::path::Path;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{self, Elevation};
+use crate::primitives::AxisElement;
+use crate::DTEDData;
+use crate::Error as DTEDError;
+
+/// Tolerance (in degrees, or fractional grid indices) for
+/// points and posts lying on the edge of a cell
+const EPS: f64 = 1e-6;
+
+#[derive(Clone, Default)]
+/// Mosaic of DTED tiles, keyed by their 1 degree cell
+///
+/// Queries are routed to the tile containing the point. Adjacent DTED tiles
+/// share the posts along their common edge, so interpolation across a seam
+/// uses the posts of a single tile. Where an edge post of that tile is void,
+/// the shared post of the adjacent tile is used instead
+///
+/// # Examples
+///
+/// ```
+/// use dted2::DTEDData;
+/// use dted2::mosaic::DTEDMosaic;
+/// use dted2::primitives::AxisElement;
+///
+/// let mosaic = DTEDMosaic::read(["tests/test_data_negative.dt2"]).unwrap();
+/// assert_eq!(mosaic.len(), 1);
+/// assert!(mosaic.get(AxisElement::new(-30, -178)).is_some());
+/// assert!(mosaic.get_elevation(-29.5, -177.5).is_some());
+/// assert!(mosaic.get_elevation(-28.5, -177.5).is_none());
+/// ```
+pub struct DTEDMosaic {
+    tiles: HashMap<AxisElement<i16>, DTEDData>,
+}
+impl DTEDMosaic {
+    /// Create an empty [DTEDMosaic]
+    pub fn new() -> DTEDMosaic {
+        DTEDMosaic::default()
+    }
+
+    /// Read DTED files into a [DTEDMosaic]
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - paths to the DTED files
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDMosaic], or the first error reading a file. Later files
+    ///   replace earlier files covering the same cell
+    pub fn read<P: AsRef<Path>, I: IntoIterator<Item = P>>(
+        paths: I,
+    ) -> Result<DTEDMosaic, DTEDError> {
+        let mut mosaic = DTEDMosaic::new();
+        for path in paths {
+            mosaic.insert(DTEDData::read(path)?);
+        }
+        Ok(mosaic)
+    }
+
+    /// Get the 1 degree cell containing a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Latitude and longitude (in integer degrees) of the lower left
+    ///   corner of the cell, see [crate::dted::DTEDGrid::origin]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::mosaic::DTEDMosaic;
+    /// use dted2::primitives::AxisElement;
+    /// assert_eq!(DTEDMosaic::cell(42.5, 15.5), AxisElement::new(42, 15));
+    /// assert_eq!(DTEDMosaic::cell(-29.5, -177.5), AxisElement::new(-30, -178));
+    /// ```
+    pub fn cell<T: Into<f64>, U: Into<f64>>(lat: T, lon: U) -> AxisElement<i16> {
+        AxisElement::new(lat.into().floor() as i16, lon.into().floor() as i16)
+    }
+
+    /// Insert a tile into the mosaic
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - [DTEDData] of the tile, keyed by the cell containing its origin
+    ///
+    /// # Returns
+    ///
+    /// * The tile previously covering the same cell, if any
+    pub fn insert(&mut self, data: DTEDData) -> Option<DTEDData> {
+        let cell = DTEDMosaic::cell(data.min.lat + EPS, data.min.lon + EPS);
+        self.tiles.insert(cell, data)
+    }
+
+    /// Remove the tile covering a cell
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - latitude and longitude (in integer degrees) of the cell
+    ///
+    /// # Returns
+    ///
+    /// * The removed tile, if any
+    pub fn remove(&mut self, cell: AxisElement<i16>) -> Option<DTEDData> {
+        self.tiles.remove(&cell)
+    }
+
+    /// Get the tile covering a cell
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - latitude and longitude (in integer degrees) of the cell
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData] of the tile, or None if there is no tile for the cell
+    pub fn get(&self, cell: AxisElement<i16>) -> Option<&DTEDData> {
+        self.tiles.get(&cell)
+    }
+
+    /// Iterate over the cells and their tiles, in arbitrary order
+    pub fn tiles(&self) -> impl Iterator<Item = (&AxisElement<i16>, &DTEDData)> {
+        self.tiles.iter()
+    }
+
+    /// Number of tiles within the mosaic
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Whether or not the mosaic contains any tiles
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Get the tile containing a lat/lon
+    ///
+    /// Points on the edge of a cell are contained by the tiles on either
+    /// side. The tile of the cell containing the point (see [DTEDMosaic::cell])
+    /// is preferred, falling back to the adjacent tiles
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [DTEDData] of the tile, or None if no tile contains the point
+    pub fn tile_at<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<&DTEDData> {
+        let lat: f64 = lat.into();
+        let lon: f64 = lon.into();
+        self.neighbourhood(DTEDMosaic::cell(lat, lon))
+            .find(|tile| tile.get_indices(lat, lon).is_some())
+    }
+
+    /// Get the elevation at a lat/lon, from any tile within the mosaic
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Elevation (in meters), or None if not covered by a tile or void,
+    ///   see [DTEDData::get_elevation]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::mosaic::DTEDMosaic;
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let mosaic: DTEDMosaic = vec![data.clone()].into_iter().collect();
+    /// assert_eq!(mosaic.get_elevation(-29.25, -177.75), data.get_elevation(-29.25, -177.75));
+    /// // the edge of the tile, within the cell to the north
+    /// assert_eq!(mosaic.get_elevation(-29.0, -177.75), data.get_elevation(-29.0, -177.75));
+    /// ```
+    pub fn get_elevation<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+        self.get_elevation_checked(lat, lon)?.value()
+    }
+
+    /// Get the elevation at a lat/lon, from any tile within the mosaic,
+    /// distinguishing voids from points which are not covered by a tile
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [Elevation], see [DTEDData::get_elevation_checked],
+    ///   or None if not covered by a tile
+    pub fn get_elevation_checked<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+    ) -> Option<Elevation> {
+        let lat: f64 = lat.into();
+        let lon: f64 = lon.into();
+        let tile = self.tile_at(lat, lon)?;
+        let indices = tile.get_indices(lat, lon)?;
+        let count = tile.metadata.count;
+        let elevation = dted::bilinear(count, indices, |lat_int, lon_int| {
+            let post = Elevation::from_post(tile.data[lon_int].elevations[lat_int]).value();
+            let on_edge = lat_int == 0
+                || lon_int == 0
+                || lat_int + 1 == count.lat as usize
+                || lon_int + 1 == count.lon as usize;
+            match post {
+                None if on_edge => self.shared_post(tile, lat_int, lon_int),
+                post => post,
+            }
+        });
+        Some(elevation.map_or(Elevation::Void, Elevation::Value))
+    }
+
+    /// Tiles of a cell and the 8 surrounding cells, starting with the cell itself
+    fn neighbourhood(&self, cell: AxisElement<i16>) -> impl Iterator<Item = &DTEDData> {
+        let offsets = [(0, 0), (-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)];
+        offsets.into_iter().filter_map(move |(d_lat, d_lon)| {
+            self.get(AxisElement::new(cell.lat + d_lat, cell.lon + d_lon))
+        })
+    }
+
+    /// Elevation of the post of an adjacent tile, which shares
+    /// the post at `(lat_int, lon_int)` of `tile`
+    fn shared_post(&self, tile: &DTEDData, lat_int: usize, lon_int: usize) -> Option<f64> {
+        let interval = tile.metadata.interval;
+        let lat = tile.min.lat + lat_int as f64 * interval.lat;
+        let lon = tile.min.lon + lon_int as f64 * interval.lon;
+        self.neighbourhood(DTEDMosaic::cell(lat, lon))
+            .filter(|other| !std::ptr::eq(*other, tile))
+            .find_map(|other| {
+                let (lat_idx, lon_idx) = other.get_indices(lat, lon)?;
+                let (lat_post, lon_post) = (lat_idx.round(), lon_idx.round());
+                if (lat_idx - lat_post).abs() > EPS || (lon_idx - lon_post).abs() > EPS {
+                    return None;
+                }
+                let post = other.data[lon_post as usize].elevations[lat_post as usize];
+                Elevation::from_post(post).value()
+            })
+    }
+}
+impl FromIterator<DTEDData> for DTEDMosaic {
+    fn from_iter<I: IntoIterator<Item = DTEDData>>(tiles: I) -> DTEDMosaic {
+        let mut mosaic = DTEDMosaic::new();
+        for data in tiles {
+            mosaic.insert(data);
+        }
+        mosaic
+    }
+}
@@ -7,9 +7,9 @@
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
-    combinator::{map, map_res, opt},
+    combinator::{cut, map, map_res, opt, peek},
     multi::count,
-    number::complete::be_u16,
+    number::complete::{be_u16, be_u32},
     sequence::{preceded, tuple},
     IResult,
 };
@@ -25,34 +25,55 @@ use crate::primitives::{Angle, AxisElement};
 // general constants
 // --------------------------------------------------
 /// Unsigned 16-bit integer sign bit
-const U16_SIGN_BIT: u16 = 0x8000;
-const U16_DATA_MSK: u16 = 0x7FFF;
+pub(crate) const U16_SIGN_BIT: u16 = 0x8000;
+pub(crate) const U16_DATA_MSK: u16 = 0x7FFF;
+/// Recognition sentinel of a DSI record, which is followed by the
+/// security classification rather than [RecognitionSentinel::DSI]'s `U`
+const DSI_SENTINEL: &[u8] = b"DSI";
+
+/// Trims the blanks (spaces or NUL bytes) padding either side of a field
+fn trim_blanks(input: &[u8]) -> &[u8] {
+    let start = input.iter().position(|b| !matches!(b, b' ' | 0)).unwrap_or(input.len());
+    let end = input.iter().rposition(|b| !matches!(b, b' ' | 0)).map_or(start, |i| i + 1);
+    &input[start..end]
+}
 
 /// Parses a byte slice into an unsigned integer
 /// - Max precision is 32 bits (4294967296)
 ///
 /// # Arguments
 ///
-/// * `input` - A byte slice
+/// * `input` - A byte slice, which may be padded with blanks on either side
 ///
 /// # Returns
 ///
-/// An option containing an unsigned integer
+/// An option containing an unsigned integer. Is None if the field is
+/// blank, if any byte between the blanks is not a digit, or if the
+/// value overflows `U`
 ///
 /// # Examples
 ///
 /// ```
 /// use dted2::parsers::to_uint;
 /// assert_eq!(to_uint::<u32>(b"123"), Some(123 as u32));
+/// assert_eq!(to_uint::<u32>(b"  30"), Some(30 as u32));
+/// assert_eq!(to_uint::<u32>(b"30  "), Some(30 as u32));
+/// assert_eq!(to_uint::<u32>(b"1 3"), None);
+/// assert_eq!(to_uint::<u32>(b"   "), None);
+/// assert_eq!(to_uint::<u8>(b"256"), None);
 /// ```
 pub fn to_uint<U>(input: &[u8]) -> Option<U>
 where
     U: PrimInt + Unsigned,
 {
-    U::from(input.iter().fold(0_u32, |acc, b| {
-        // assert!(*b >= 0x30 && *b <= 0x39); // is a digit
-        (acc * 10) + (*b - 0x30) as u32
-    }))
+    let digits = trim_blanks(input);
+    if digits.is_empty() {
+        return None;
+    }
+    U::from(digits.iter().try_fold(0_u32, |acc, b| match b.is_ascii_digit() {
+        true => acc.checked_mul(10)?.checked_add((*b - b'0') as u32),
+        false => None,
+    })?)
 }
 
 /// Nom parser that parses `count` number of bytes and returns an unsigned integer
@@ -71,6 +92,7 @@ where
 /// ```
 /// use dted2::parsers::uint_parser;
 /// assert_eq!(uint_parser::<u32>(3)(b"123"), Ok((&b""[..], 123 as u32)));
+/// assert_eq!(uint_parser::<u32>(4)(b"  30"), Ok((&b""[..], 30 as u32)));
 /// ```
 pub fn uint_parser<U>(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], U>
 where
@@ -144,12 +166,7 @@ pub fn to_angle(
         uint_parser_with_default(num_deg, 0u32),
         uint_parser_with_default(num_min, 0u32),
         uint_parser_with_default(num_sec, 0u32),
-        opt(alt((
-            map(tag("N"), |_| false),
-            map(tag("S"), |_| true),
-            map(tag("E"), |_| false),
-            map(tag("W"), |_| true),
-        ))),
+        hemisphere_parser,
     ))(input)?;
     Ok((
         input,
@@ -181,9 +198,170 @@ pub fn angle_parser(
     move |input| to_angle(input, num_deg, num_min, num_sec)
 }
 
+/// Parses a byte slice into a [crate::primitives::Angle], where the seconds
+/// contain a decimal point followed by `num_frac` fractional digits
+/// (e.g. `DDMMSS.SH`)
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+/// * `num_deg` - The number of bytes to parse for degrees
+/// * `num_min` - The number of bytes to parse for minutes
+/// * `num_sec` - The number of bytes to parse for (integer) seconds
+/// * `num_frac` - The number of bytes to parse for fractional seconds
+///
+/// # Returns
+///
+/// An [Option] containing a [crate::primitives::Angle]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::to_decimal_angle;
+/// use dted2::primitives::Angle;
+/// assert_eq!(to_decimal_angle(b"300000.0S", 2, 2, 2, 1), Ok((&b""[..], Angle::new(30, 0, 0.0, true))));
+/// assert_eq!(to_decimal_angle(b"1234512.5E", 3, 2, 2, 1), Ok((&b""[..], Angle::new(123, 45, 12.5, false))));
+/// ```
+pub fn to_decimal_angle(
+    input: &[u8],
+    num_deg: usize,
+    num_min: usize,
+    num_sec: usize,
+    num_frac: usize,
+) -> IResult<&[u8], Angle> {
+    let (input, (deg, min, sec, frac, sign)) = tuple((
+        uint_parser_with_default(num_deg, 0u32),
+        uint_parser_with_default(num_min, 0u32),
+        uint_parser_with_default(num_sec, 0u32),
+        preceded(tag("."), uint_parser_with_default(num_frac, 0u32)),
+        hemisphere_parser,
+    ))(input)?;
+    let sec = sec as f64 + frac as f64 / 10f64.powi(num_frac as i32);
+    Ok((
+        input,
+        Angle::new(deg as u16, min as u8, sec, sign.unwrap_or(false)),
+    ))
+}
+
+/// Nom parser that parses an angle with fractional seconds
+/// (see [to_decimal_angle])
+///
+/// # Arguments
+///
+/// * `num_deg` - The number of bytes to parse for degrees
+/// * `num_min` - The number of bytes to parse for minutes
+/// * `num_sec` - The number of bytes to parse for (integer) seconds
+/// * `num_frac` - The number of bytes to parse for fractional seconds
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::Angle;
+/// use dted2::parsers::decimal_angle_parser;
+/// assert_eq!(decimal_angle_parser(3, 2, 2, 1)(b"0000000.0"), Ok((&b""[..], Angle::new(0, 0, 0.0, false))));
+/// assert_eq!(decimal_angle_parser(3, 2, 2, 1)(b"1780000.0W"), Ok((&b""[..], Angle::new(178, 0, 0.0, true))));
+/// ```
+pub fn decimal_angle_parser(
+    num_deg: usize,
+    num_min: usize,
+    num_sec: usize,
+    num_frac: usize,
+) -> impl Fn(&[u8]) -> IResult<&[u8], Angle> {
+    move |input| to_decimal_angle(input, num_deg, num_min, num_sec, num_frac)
+}
+
+/// Nom parser for an optional hemisphere character, returning
+/// whether or not the angle is negative (`S` or `W`)
+fn hemisphere_parser(input: &[u8]) -> IResult<&[u8], Option<bool>> {
+    opt(alt((
+        map(tag("N"), |_| false),
+        map(tag("S"), |_| true),
+        map(tag("E"), |_| false),
+        map(tag("W"), |_| true),
+    )))(input)
+}
+
+/// Nom parser that parses `count` bytes as a text field, trimming
+/// any blank (space or NUL) padding
+///
+/// # Arguments
+///
+/// * `count` - The number of bytes to parse
+///
+/// # Returns
+///
+/// An [Option] containing the trimmed text, otherwise, if the
+/// field is blank, returns [Option::None]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::text_parser;
+/// assert_eq!(text_parser(6)(b"DTED2 xx"), Ok((&b"xx"[..], Some("DTED2".to_string()))));
+/// assert_eq!(text_parser(4)(b"    "), Ok((&b""[..], None)));
+/// ```
+pub fn text_parser(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], Option<String>> {
+    move |input| {
+        map(take(count), |bytes: &[u8]| {
+            let text = String::from_utf8_lossy(bytes);
+            let text = text.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+            match text.is_empty() {
+                true => None,
+                false => Some(text.to_string()),
+            }
+        })(input)
+    }
+}
+
+/// Nom parser for optional numeric fields in DTED. If the field
+/// is blank (or a valid NAN value), [Option::None] is returned,
+/// otherwise the value (unsigned integer) is returned as [Option::Some]
+///
+/// # Arguments
+///
+/// * `count` - The number of bytes to parse
+///
+/// # Returns
+///
+/// An [Option] containing an unsigned integer,
+/// otherwise, if blank or a valid NAN, returns [Option::None]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::opt_uint_parser;
+/// assert_eq!(opt_uint_parser::<u16>(4)(b"0123"), Ok((&b""[..], Some(123))));
+/// assert_eq!(opt_uint_parser::<u16>(4)(b"  30"), Ok((&b""[..], Some(30))));
+/// assert_eq!(opt_uint_parser::<u16>(4)(b"    "), Ok((&b""[..], None)));
+/// assert_eq!(opt_uint_parser::<u16>(4)(b"NA  "), Ok((&b""[..], None)));
+/// assert!(opt_uint_parser::<u16>(4)(b"12AB").is_err());
+/// ```
+pub fn opt_uint_parser<U>(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], Option<U>>
+where
+    U: PrimInt + Unsigned,
+{
+    move |input| {
+        let (rest, bytes) = take(count)(input)?;
+        let digits = trim_blanks(bytes);
+        if digits.is_empty() || is_nan(digits) {
+            return Ok((rest, None));
+        }
+        match to_uint::<U>(digits) {
+            Some(x) => Ok((rest, Some(x))),
+            None => Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Digit,
+            ))),
+        }
+    }
+}
+
 /// Parses a byte slice into an unsigned integer,
 /// if the value is not a valid NAN DTED value
 ///
+/// A valid NAN value contains `NA` anywhere within the field,
+/// as some producers pad the field (e.g. `NA  ` or `00NA`)
+///
 /// # Arguments
 ///
 /// * `input` - A byte slice
@@ -198,26 +376,25 @@ pub fn angle_parser(
 /// ```
 /// use dted2::parsers::to_nan;
 /// assert_eq!(to_nan::<u32>(b"NA$$", 4), Ok((&b""[..], None)));
+/// assert_eq!(to_nan::<u32>(b"00NA", 4), Ok((&b""[..], None)));
 /// assert_eq!(to_nan::<u32>(b"12345", 4), Ok((&b"5"[..], Some(1234 as u32))));
 /// ```
 pub fn to_nan<U>(input: &[u8], count: usize) -> IResult<&[u8], Option<U>>
 where
     U: PrimInt + Unsigned,
 {
-    match tag::<_, _, nom::error::Error<_>>(RecognitionSentinel::NA.value())(input) {
-        Ok((input, _)) => {
-            let (input, _) = take(count - 2)(input)?;
-            Ok((input, None))
-        }
-        Err(e) => match e {
-            nom::Err::Error(err_input) => {
-                uint_parser::<U>(count)(err_input.input).map(|(input, x)| (input, Some(x)))
-            }
-            _ => Err(e),
-        },
+    let (rest, bytes) = take(count)(input)?;
+    match is_nan(bytes) {
+        true => Ok((rest, None)),
+        false => uint_parser::<U>(count)(input).map(|(input, x)| (input, Some(x))),
     }
 }
 
+/// Whether or not a field contains the NAN ([RecognitionSentinel::NA]) marker
+fn is_nan(bytes: &[u8]) -> bool {
+    bytes.windows(2).any(|w| w == RecognitionSentinel::NA.value())
+}
+
 /// Nom parser for NAN (either Not a Number or Not Available) values in DTED
 /// If not a valid NAN value, then the value (unsigned integer)
 /// is returned as [Option::Some], otherwise [Option::None]
@@ -238,9 +415,7 @@ where
 /// assert_eq!(nan_parser::<u32>(4)(b"NA$$"), Ok((&b""[..], None)));
 /// assert_eq!(nan_parser::<u32>(4)(b"12345"), Ok((&b"5"[..], Some(1234 as u32))));
 /// ```
-pub fn nan_parser<U>(
-    count: usize,
-) -> impl Fn(&[u8]) -> Result<(&[u8], Option<U>), nom::Err<nom::error::Error<&[u8]>>>
+pub fn nan_parser<U>(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], Option<U>>
 where
     U: PrimInt + Unsigned,
 {
@@ -310,6 +485,36 @@ pub fn signed_mag_parser(input: &[u8]) -> IResult<&[u8], i16> {
     })(input)
 }
 
+/// Nom parser for a [SecurityCode] field of `count` bytes, where the
+/// code is the first byte. If the field is blank, [Option::None] is returned
+///
+/// # Arguments
+///
+/// * `count` - The number of bytes to parse
+///
+/// # Returns
+///
+/// An [Option] containing a [SecurityCode], or an error if the
+/// code is not recognized
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::SecurityCode;
+/// use dted2::parsers::security_code_parser;
+/// assert_eq!(security_code_parser(3)(b"U  "), Ok((&b""[..], Some(SecurityCode::Unclassified))));
+/// assert_eq!(security_code_parser(3)(b"   "), Ok((&b""[..], None)));
+/// assert!(security_code_parser(3)(b"X  ").is_err());
+/// ```
+pub fn security_code_parser(count: usize) -> impl Fn(&[u8]) -> IResult<&[u8], Option<SecurityCode>> {
+    move |input| {
+        map_res(take(count), |bytes: &[u8]| match bytes.first() {
+            None | Some(b' ') | Some(0) => Ok(None),
+            Some(&code) => SecurityCode::try_from(code).map(Some),
+        })(input)
+    }
+}
+
 /// Nom parser for a [RawDTEDHeader]
 ///
 /// # Arguments
@@ -328,11 +533,16 @@ pub fn signed_mag_parser(input: &[u8]) -> IResult<&[u8], i16> {
 /// use dted2::parsers::dted_uhl_parser;
 /// use dted2::dted::RecognitionSentinel;
 ///
+/// use dted2::dted::SecurityCode;
+///
 /// assert_eq!(dted_uhl_parser(b"UHL11234556E8901234W123456789012UUUXXXXXXXXXXXX123445670XXXXXXXXXXXXXXXXXXXXXXXX"), Ok((&b""[..], RawDTEDHeader {
 ///     origin: AxisElement { lat: Angle::new(890, 12, 34.0, true), lon: Angle::new(123, 45, 56.0, false) },
 ///     interval_secs_x_10: AxisElement { lat: 5678, lon: 1234 },
 ///     accuracy: Some(9012),
+///     security_code: Some(SecurityCode::Unclassified),
+///     reference: Some("XXXXXXXXXXXX".to_string()),
 ///     count: AxisElement { lat: 4567, lon: 1234 },
+///     multiple_accuracy: false,
 /// })));
 /// ```
 pub fn dted_uhl_parser(input: &[u8]) -> IResult<&[u8], RawDTEDHeader> {
@@ -351,9 +561,11 @@ pub fn dted_uhl_parser(input: &[u8]) -> IResult<&[u8], RawDTEDHeader> {
             lon_interval_s,
             lat_interval_s,
             accuracy,
-            _,
+            security_code,
+            reference,
             lon_count,
             lat_count,
+            multiple_accuracy,
             _,
         ),
     ) = tuple((
@@ -362,10 +574,12 @@ pub fn dted_uhl_parser(input: &[u8]) -> IResult<&[u8], RawDTEDHeader> {
         uint_parser(4),
         uint_parser(4),
         nan_parser(4),
-        take(15_usize),
+        security_code_parser(3),
+        text_parser(12),
         uint_parser(4),
         uint_parser(4),
-        take(25_usize),
+        map(take(1_usize), |flag: &[u8]| flag[0] == b'1'),
+        take(24_usize),
     ))(input)?;
     // --------------------------------------------------
     // return
@@ -376,22 +590,390 @@ pub fn dted_uhl_parser(input: &[u8]) -> IResult<&[u8], RawDTEDHeader> {
             origin: AxisElement::new(lat_origin, lon_origin),
             interval_secs_x_10: AxisElement::new(lat_interval_s, lon_interval_s),
             accuracy,
+            security_code,
+            reference,
             count: AxisElement::new(lat_count, lon_count),
+            multiple_accuracy,
         },
     ))
 }
 
+/// Nom parser for a [DTEDRecordDSI]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [DTEDRecordDSI] parsed from the byte slice
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{ SecurityCode, DT2_DSI_RECORD_LENGTH };
+/// use dted2::parsers::dted_dsi_parser;
+/// use dted2::primitives::{ Angle, AxisElement };
+///
+/// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let (rest, dsi) = dted_dsi_parser(&raw[80..]).unwrap();
+/// assert_eq!(raw.len() - rest.len(), 80 + DT2_DSI_RECORD_LENGTH);
+/// assert_eq!(dsi.security_code, Some(SecurityCode::Unclassified));
+/// assert_eq!(dsi.version, Some("DTED0".to_string()));
+/// assert_eq!(dsi.edition, None);
+/// assert_eq!(dsi.vertical_datum, Some("MSL".to_string()));
+/// assert_eq!(dsi.origin, AxisElement::new(Angle::new(30, 0, 0.0, true), Angle::new(178, 0, 0.0, true)));
+/// assert_eq!(dsi.ne_corner, AxisElement::new(Angle::new(29, 0, 0.0, true), Angle::new(177, 0, 0.0, true)));
+/// assert_eq!(dsi.interval_secs_x_10, AxisElement::new(300, 300));
+/// assert_eq!(dsi.count, AxisElement::new(121, 121));
+/// assert_eq!(dsi.coverage(), 1.0);
+/// ```
+pub fn dted_dsi_parser(input: &[u8]) -> IResult<&[u8], DTEDRecordDSI> {
+    // --------------------------------------------------
+    // verify is DSI
+    // --------------------------------------------------
+    let (input, _) = tag(DSI_SENTINEL)(input)?;
+    // --------------------------------------------------
+    // parse security + identification
+    // --------------------------------------------------
+    let (
+        input,
+        (
+            security_code,
+            security_release,
+            security_handling,
+            _,
+            version,
+            reference,
+            _,
+            edition,
+            mm_version,
+            maintenance_date,
+            mm_date,
+            maintenance_code,
+            producer_code,
+            _,
+        ),
+    ) = tuple((
+        security_code_parser(1),
+        text_parser(2),
+        text_parser(27),
+        take(26_usize),
+        text_parser(5),
+        text_parser(15),
+        take(8_usize),
+        opt_uint_parser(2),
+        map(take(1_usize), |c: &[u8]| match c[0] {
+            b' ' | 0 => None,
+            c => Some(c as char),
+        }),
+        opt_uint_parser(4),
+        opt_uint_parser(4),
+        opt_uint_parser(4),
+        text_parser(8),
+        take(16_usize),
+    ))(input)?;
+    // --------------------------------------------------
+    // parse product specification
+    // --------------------------------------------------
+    let (
+        input,
+        (
+            product_specs_desc,
+            product_specs_code,
+            product_specs_date,
+            vertical_datum,
+            horizontal_datum,
+            collection_system,
+            compilation_date,
+            _,
+        ),
+    ) = tuple((
+        text_parser(9),
+        opt_uint_parser(2),
+        opt_uint_parser(4),
+        text_parser(3),
+        text_parser(5),
+        text_parser(10),
+        opt_uint_parser(4),
+        take(22_usize),
+    ))(input)?;
+    // --------------------------------------------------
+    // parse geographic extent
+    // --------------------------------------------------
+    let (
+        input,
+        (
+            lat_origin,
+            lon_origin,
+            lat_sw,
+            lon_sw,
+            lat_nw,
+            lon_nw,
+            lat_ne,
+            lon_ne,
+            lat_se,
+            lon_se,
+            clockwise_orientation,
+            lat_interval_s,
+            lon_interval_s,
+            lat_count,
+            lon_count,
+            partial_cell_flag,
+            _,
+        ),
+    ) = tuple((
+        decimal_angle_parser(2, 2, 2, 1),
+        decimal_angle_parser(3, 2, 2, 1),
+        angle_parser(2, 2, 2),
+        angle_parser(3, 2, 2),
+        angle_parser(2, 2, 2),
+        angle_parser(3, 2, 2),
+        angle_parser(2, 2, 2),
+        angle_parser(3, 2, 2),
+        angle_parser(2, 2, 2),
+        angle_parser(3, 2, 2),
+        decimal_angle_parser(3, 2, 2, 1),
+        uint_parser(4),
+        uint_parser(4),
+        uint_parser(4),
+        uint_parser(4),
+        uint_parser(2),
+        take(357_usize),
+    ))(input)?;
+    // --------------------------------------------------
+    // return
+    // --------------------------------------------------
+    Ok((
+        input,
+        DTEDRecordDSI {
+            security_code,
+            security_release,
+            security_handling,
+            version,
+            reference,
+            edition,
+            mm_version,
+            maintenance_date,
+            mm_date,
+            maintenance_code,
+            producer_code,
+            product_specs_desc,
+            product_specs_code,
+            product_specs_date,
+            vertical_datum,
+            horizontal_datum,
+            collection_system,
+            compilation_date,
+            origin: AxisElement::new(lat_origin, lon_origin),
+            sw_corner: AxisElement::new(lat_sw, lon_sw),
+            nw_corner: AxisElement::new(lat_nw, lon_nw),
+            ne_corner: AxisElement::new(lat_ne, lon_ne),
+            se_corner: AxisElement::new(lat_se, lon_se),
+            clockwise_orientation,
+            interval_secs_x_10: AxisElement::new(lat_interval_s, lon_interval_s),
+            count: AxisElement::new(lat_count, lon_count),
+            partial_cell_flag,
+        },
+    ))
+}
+
+/// Nom parser for the optional [DTEDRecordDSI] following the
+/// [RawDTEDHeader]. If the DSI sentinel is not present, [Option::None]
+/// is returned, otherwise the record must parse successfully
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// An [Option] containing a [DTEDRecordDSI]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::opt_dted_dsi_parser;
+/// assert_eq!(opt_dted_dsi_parser(b""), Ok((&b""[..], None)));
+/// assert!(opt_dted_dsi_parser(b"DSIU").is_err());
+/// assert!(opt_dted_dsi_parser(b"DSIX").is_err());
+/// ```
+pub fn opt_dted_dsi_parser(input: &[u8]) -> IResult<&[u8], Option<DTEDRecordDSI>> {
+    opt_record_parser(DSI_SENTINEL, dted_dsi_parser)(input)
+}
+
+/// Nom parser for a [DTEDAccuracy], made up of four 4-byte fields
+/// (absolute horizontal, absolute vertical, relative horizontal,
+/// and relative vertical accuracy), any of which may be NA or blank
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [DTEDAccuracy] parsed from the byte slice
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DTEDAccuracy;
+/// use dted2::parsers::accuracy_parser;
+/// assert_eq!(accuracy_parser(b"0020NA  0012    "), Ok((&b""[..], DTEDAccuracy {
+///     abs_horizontal: Some(20),
+///     abs_vertical: None,
+///     rel_horizontal: Some(12),
+///     rel_vertical: None,
+/// })));
+/// ```
+pub fn accuracy_parser(input: &[u8]) -> IResult<&[u8], DTEDAccuracy> {
+    let (input, (abs_horizontal, abs_vertical, rel_horizontal, rel_vertical)) = tuple((
+        opt_uint_parser(4),
+        opt_uint_parser(4),
+        opt_uint_parser(4),
+        opt_uint_parser(4),
+    ))(input)?;
+    Ok((
+        input,
+        DTEDAccuracy {
+            abs_horizontal,
+            abs_vertical,
+            rel_horizontal,
+            rel_vertical,
+        },
+    ))
+}
+
+/// Nom parser for a single [DTEDAccuracySubRegion] within the ACC record
+///
+/// Each sub-region is made up of a [DTEDAccuracy], the number of coordinates
+/// in the outline (3 - 14), and space for 14 coordinate pairs, of which
+/// only the first `n` are used
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [DTEDAccuracySubRegion] parsed from the byte slice
+pub fn accuracy_sub_region_parser(input: &[u8]) -> IResult<&[u8], DTEDAccuracySubRegion> {
+    let (input, (accuracy, num_coords)) = tuple((accuracy_parser, uint_parser::<u8>(2)))(input)?;
+    let num_coords = (num_coords as usize).min(DT2_ACC_MAX_OUTLINE_COORDS);
+    let (input, (outline, _)) = tuple((
+        count(
+            map(
+                tuple((
+                    decimal_angle_parser(2, 2, 2, 1),
+                    decimal_angle_parser(3, 2, 2, 1),
+                )),
+                |(lat, lon)| AxisElement::new(lat, lon),
+            ),
+            num_coords,
+        ),
+        take((DT2_ACC_MAX_OUTLINE_COORDS - num_coords) * DT2_ACC_COORD_LENGTH),
+    ))(input)?;
+    Ok((input, DTEDAccuracySubRegion { accuracy, outline }))
+}
+
+/// Nom parser for a [DTEDRecordACC]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [DTEDRecordACC] parsed from the byte slice
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::{ DT2_DSI_RECORD_LENGTH, DT2_ACC_RECORD_LENGTH };
+/// use dted2::parsers::dted_acc_parser;
+///
+/// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let (rest, acc) = dted_acc_parser(&raw[80 + DT2_DSI_RECORD_LENGTH..]).unwrap();
+/// assert_eq!(raw.len() - rest.len(), 80 + DT2_DSI_RECORD_LENGTH + DT2_ACC_RECORD_LENGTH);
+/// assert_eq!(acc.accuracy.abs_vertical, None);
+/// assert_eq!(acc.multiple_accuracy_flag, 0);
+/// assert!(acc.sub_regions.is_empty());
+/// ```
+pub fn dted_acc_parser(input: &[u8]) -> IResult<&[u8], DTEDRecordACC> {
+    // --------------------------------------------------
+    // verify is ACC
+    // --------------------------------------------------
+    let (input, _) = tag(RecognitionSentinel::ACC.value())(input)?;
+    // --------------------------------------------------
+    // parse accuracy of the entire cell
+    // --------------------------------------------------
+    let (input, (accuracy, _, multiple_accuracy_flag)) = tuple((
+        accuracy_parser,
+        take(36_usize),
+        opt_uint_parser::<u8>(2),
+    ))(input)?;
+    // --------------------------------------------------
+    // parse the accuracy sub-regions
+    // --------------------------------------------------
+    let multiple_accuracy_flag = multiple_accuracy_flag.unwrap_or(0);
+    let num_regions = (multiple_accuracy_flag as usize).min(DT2_ACC_MAX_SUB_REGIONS);
+    let (input, (sub_regions, _)) = tuple((
+        count(accuracy_sub_region_parser, num_regions),
+        take((DT2_ACC_MAX_SUB_REGIONS - num_regions) * DT2_ACC_SUB_REGION_LENGTH + 87),
+    ))(input)?;
+    // --------------------------------------------------
+    // return
+    // --------------------------------------------------
+    Ok((
+        input,
+        DTEDRecordACC {
+            accuracy,
+            multiple_accuracy_flag,
+            sub_regions,
+        },
+    ))
+}
+
+/// Nom parser for the optional [DTEDRecordACC] following the
+/// [DTEDRecordDSI]. If the ACC sentinel is not present, [Option::None]
+/// is returned, otherwise the record must parse successfully
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// An [Option] containing a [DTEDRecordACC]
+pub fn opt_dted_acc_parser(input: &[u8]) -> IResult<&[u8], Option<DTEDRecordACC>> {
+    opt_record_parser(RecognitionSentinel::ACC.value(), dted_acc_parser)(input)
+}
+
+/// Nom parser for an optional header record, identified by its
+/// recognition sentinel. Once the sentinel is matched, any failure
+/// of `parser` is returned rather than being treated as absent
+fn opt_record_parser<'a, O>(
+    sentinel: &'static [u8],
+    parser: fn(&'a [u8]) -> IResult<&'a [u8], O>,
+) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], Option<O>> {
+    move |input| opt(preceded(peek(tag(sentinel)), cut(parser)))(input)
+}
+
+/// Nom parser for a [RawDTEDFile]
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+///
+/// # Returns
+///
+/// A [RawDTEDFile] parsed from the byte slice
 pub fn dted_file_parser(input: &[u8]) -> IResult<&[u8], RawDTEDFile> {
     // --------------------------------------------------
     // get headers and header records
     // --------------------------------------------------
-    let (input, (header, _dsi_record, _acc_record)) = tuple((
-        dted_uhl_parser,
-        // TODO: parse DSI record
-        // TODO: parse ACC record
-        take(DT2_DSI_RECORD_LENGTH),
-        take(DT2_ACC_RECORD_LENGTH),
-    ))(input)?;
+    let (input, (header, dsi_record, acc_record)) =
+        tuple((dted_uhl_parser, dted_dsi_parser, dted_acc_parser))(input)?;
     // --------------------------------------------------
     // parse the actual data
     // --------------------------------------------------
@@ -407,25 +989,91 @@ pub fn dted_file_parser(input: &[u8]) -> IResult<&[u8], RawDTEDFile> {
         RawDTEDFile {
             header,
             data: records,
-            dsi_record: None,
-            acc_record: None,
+            dsi_record: Some(dsi_record),
+            acc_record: Some(acc_record),
         },
     ))
 }
 
+/// Computes the checksum of a DTED data record, which is the sum
+/// of all bytes within the record (starting from the sentinel and
+/// ending with the last elevation), as an unsigned 32-bit integer
+///
+/// # Arguments
+///
+/// * `input` - A byte slice of the record, excluding the checksum itself
+///
+/// # Returns
+///
+/// The checksum
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::dted_record_checksum;
+/// assert_eq!(dted_record_checksum(&[0xAA, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00]), 172);
+/// ```
+pub fn dted_record_checksum(input: &[u8]) -> u32 {
+    input.iter().map(|&b| b as u32).sum()
+}
+
 // Parse a DTED record
 pub fn parse_dted_record(input: &[u8], line_len: usize) -> IResult<&[u8], RawDTEDRecord> {
-    let (input, (block_byte0, block_rest, lon_count, lat_count, elevations, _)) = tuple((
-        preceded(
-            tag(RecognitionSentinel::DATA.value()),
-            take(1_usize), // starting block byte size, will always be 0
-        ),
-        be_u16,
-        be_u16,
-        be_u16,
-        count(signed_mag_parser, line_len),
-        take(4_usize), // checksum
-    ))(input)?;
+    parse_dted_record_rows(input, line_len, 0..line_len)
+}
+
+/// Parse a DTED record, only decoding the elevations within `rows`.
+/// The remaining elevations are skipped
+///
+/// # Arguments
+///
+/// * `input` - A byte slice
+/// * `line_len` - number of elevations (latitude points) within the record
+/// * `rows` - range of latitude indices to decode, within `0..line_len`
+///
+/// # Returns
+///
+/// A [RawDTEDRecord] containing only the elevations within `rows`,
+/// or an error if `rows` is not within `0..line_len`
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DT2_HEADER_LENGTH;
+/// use dted2::parsers::{ parse_dted_record, parse_dted_record_rows };
+///
+/// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let (_, full) = parse_dted_record(&raw[DT2_HEADER_LENGTH..], 121).unwrap();
+/// let (_, rows) = parse_dted_record_rows(&raw[DT2_HEADER_LENGTH..], 121, 10..20).unwrap();
+/// assert_eq!(rows.elevations, full.elevations[10..20]);
+/// assert_eq!(rows.checksum, full.checksum);
+/// assert!(parse_dted_record_rows(&raw[DT2_HEADER_LENGTH..], 121, 100..130).is_err());
+/// ```
+pub fn parse_dted_record_rows(
+    input: &[u8],
+    line_len: usize,
+    rows: std::ops::Range<usize>,
+) -> IResult<&[u8], RawDTEDRecord> {
+    if rows.start > rows.end || rows.end > line_len {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    let (input, (block_byte0, block_rest, lon_count, lat_count, _, elevations, _, checksum)) =
+        tuple((
+            preceded(
+                tag(RecognitionSentinel::DATA.value()),
+                take(1_usize), // starting block byte size, will always be 0
+            ),
+            be_u16,
+            be_u16,
+            be_u16,
+            take(2 * rows.start),
+            count(signed_mag_parser, rows.len()),
+            take(2 * (line_len - rows.end)),
+            be_u32,
+        ))(input)?;
     // --------------------------------------------------
     // return
     // --------------------------------------------------
@@ -436,6 +1084,7 @@ pub fn parse_dted_record(input: &[u8], line_len: usize) -> IResult<&[u8], RawDTE
             lon_count,
             lat_count,
             elevations,
+            checksum,
         },
     ))
 }
@@ -371,7 +371,7 @@ impl_type_from_angle!(i64);
 impl_type_from_angle!(i128);
 impl_type_from_angle!(isize);
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 /// An Axis element
 ///
 /// # Fields
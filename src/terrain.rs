@@ -0,0 +1,1018 @@
+//! Terrain analysis over any source of elevations, such as a single tile
+//! or a mosaic of tiles.
+//!
+//! The main entry point is [`ElevationSource`].
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::{self, Elevation};
+use crate::mosaic::DTEDMosaic;
+use crate::primitives::AxisElement;
+use crate::DTEDData;
+
+/// Mean radius of the Earth in meters (IUGG), used for great-circle distances
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Source of elevations, such as [DTEDData] or [DTEDMosaic]
+///
+/// Implementors provide the elevation at any lat/lon, from which the
+/// terrain analysis (e.g. [ElevationSource::profile]) is provided
+pub trait ElevationSource {
+    /// Get the elevation at a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * [Elevation], or None if the point is not covered
+    fn elevation(&self, lat: f64, lon: f64) -> Option<Elevation>;
+
+    /// Interval between the posts (in degrees) near a lat/lon
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Latitude and longitude interval, or None if unknown
+    fn post_interval(&self, lat: f64, lon: f64) -> Option<AxisElement<f64>>;
+
+    /// Sample the elevations along the great circle between two points
+    ///
+    /// The samples are evenly spaced, from `start` to `end` inclusive,
+    /// at most [ProfileSpacing] apart
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - latitude and longitude of the first sample
+    /// * `end` - latitude and longitude of the last sample
+    /// * `spacing` - [ProfileSpacing] between the samples
+    ///
+    /// # Returns
+    ///
+    /// * [ProfileSample]s, ordered by distance from `start`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::primitives::AxisElement;
+    /// use dted2::terrain::{ ElevationSource, ProfileSpacing };
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let start = AxisElement::new(-29.9, -177.9);
+    /// let end = AxisElement::new(-29.1, -177.1);
+    /// let profile = data.profile(start, end, ProfileSpacing::Meters(100.0));
+    /// assert_eq!(profile[0].position, start);
+    /// let last = profile.last().unwrap();
+    /// assert!((last.distance - 117_929.4).abs() < 0.1);
+    /// assert!(profile.windows(2).all(|w| w[1].distance - w[0].distance <= 100.0));
+    /// assert!(profile.iter().all(|sample| sample.elevation.is_some()));
+    /// ```
+    fn profile(
+        &self,
+        start: AxisElement<f64>,
+        end: AxisElement<f64>,
+        spacing: ProfileSpacing,
+    ) -> Vec<ProfileSample> {
+        let distance = great_circle_distance(start, end);
+        let spacing = match spacing {
+            ProfileSpacing::Meters(meters) => Some(meters),
+            ProfileSpacing::Posts => self
+                .post_interval(start.lat, start.lon)
+                .or_else(|| self.post_interval(end.lat, end.lon))
+                .map(|interval| post_spacing(interval, start.lat.abs().max(end.lat.abs()))),
+        };
+        // --------------------------------------------------
+        // number of segments, at most `spacing` meters long
+        // --------------------------------------------------
+        let segments = match spacing {
+            Some(spacing) if spacing > 0.0 => (distance / spacing).ceil().max(1.0) as usize,
+            _ => 1,
+        };
+        let samples = if distance > 0.0 { segments + 1 } else { 1 };
+        (0..samples)
+            .map(|i| {
+                let fraction = i as f64 / segments as f64;
+                let position = match i {
+                    0 => start,
+                    i if i == segments => end,
+                    _ => great_circle_point(start, end, fraction),
+                };
+                ProfileSample {
+                    distance: distance * fraction,
+                    position,
+                    elevation: self.elevation(position.lat, position.lon),
+                }
+            })
+            .collect()
+    }
+
+    /// Determine whether a target is visible from an observer, along the
+    /// [ElevationSource::profile] between them
+    ///
+    /// The sight line is straight between the altitudes of the observer and
+    /// the target, and is obstructed wherever it passes below the terrain
+    /// between them (optionally raised by the curvature of the earth, see
+    /// [LineOfSightOptions]). Void samples, or samples which are not
+    /// covered by the source, are skipped, see [LineOfSight::voids]
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - [Viewpoint] of the observer
+    /// * `target` - [Viewpoint] of the target
+    /// * `options` - [LineOfSightOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [LineOfSight], or None if the altitude of the observer or the target
+    ///   is unknown, i.e. a [Height::AboveGround] over a void or uncovered point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::terrain::{ ElevationSource, Height, LineOfSightOptions, Viewpoint };
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let options = LineOfSightOptions::default();
+    /// let observer = Viewpoint::new(-29.9, -177.9, Height::AboveMsl(10_000.0));
+    /// let target = Viewpoint::new(-29.1, -177.1, Height::AboveGround(2.0));
+    /// let sight = data.line_of_sight(observer, target, &options).unwrap();
+    /// assert!(sight.visible);
+    /// assert!(sight.obstruction.is_none());
+    /// assert!(sight.clearance > 0.0);
+    ///
+    /// // below sea level, obstructed by the terrain (or sea level) in between
+    /// let observer = Viewpoint::new(-29.9, -177.9, Height::AboveMsl(-100.0));
+    /// let target = Viewpoint::new(-29.1, -177.1, Height::AboveMsl(-100.0));
+    /// let sight = data.line_of_sight(observer, target, &options).unwrap();
+    /// assert!(!sight.visible);
+    /// assert!(sight.obstruction.unwrap().distance > 0.0);
+    /// assert!(sight.clearance < 0.0);
+    /// ```
+    fn line_of_sight(
+        &self,
+        observer: Viewpoint,
+        target: Viewpoint,
+        options: &LineOfSightOptions,
+    ) -> Option<LineOfSight> {
+        let observer_altitude = observer.altitude(self)?;
+        let target_altitude = target.altitude(self)?;
+        let profile = self.profile(observer.position, target.position, options.spacing);
+        Some(sight_line(&profile, observer_altitude, target_altitude, options))
+    }
+}
+impl ElevationSource for DTEDData {
+    fn elevation(&self, lat: f64, lon: f64) -> Option<Elevation> {
+        self.get_elevation_checked(lat, lon)
+    }
+
+    fn post_interval(&self, _lat: f64, _lon: f64) -> Option<AxisElement<f64>> {
+        Some(self.metadata.interval)
+    }
+}
+impl ElevationSource for DTEDMosaic {
+    fn elevation(&self, lat: f64, lon: f64) -> Option<Elevation> {
+        self.get_elevation_checked(lat, lon)
+    }
+
+    /// Interval of the tile containing the point, or else
+    /// the finest interval of any tile within the mosaic
+    fn post_interval(&self, lat: f64, lon: f64) -> Option<AxisElement<f64>> {
+        match self.tile_at(lat, lon) {
+            Some(tile) => Some(tile.metadata.interval),
+            None => self
+                .tiles()
+                .map(|(_, tile)| tile.metadata.interval)
+                .reduce(|a, b| AxisElement::new(a.lat.min(b.lat), a.lon.min(b.lon))),
+        }
+    }
+}
+#[cfg(feature = "mmap")]
+impl ElevationSource for crate::mmap::DTEDMmap {
+    fn elevation(&self, lat: f64, lon: f64) -> Option<Elevation> {
+        self.get_indices(lat, lon)?;
+        Some(self.get_elevation(lat, lon).map_or(Elevation::Void, Elevation::Value))
+    }
+
+    fn post_interval(&self, _lat: f64, _lon: f64) -> Option<AxisElement<f64>> {
+        Some(self.metadata.interval)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Spacing between the samples of a profile, see [ElevationSource::profile]
+pub enum ProfileSpacing {
+    /// Maximum distance between samples in meters
+    Meters(f64),
+    /// The distance between posts, i.e. the smaller of the latitude and
+    /// longitude intervals (see [ElevationSource::post_interval]) at the
+    /// end of the path furthest from the equator
+    Posts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Sample of a profile, see [ElevationSource::profile]
+///
+/// # Fields
+///
+/// * `distance` - great-circle distance from the start of the profile in meters
+/// * `position` - latitude and longitude of the sample
+/// * `elevation` - [Elevation] of the sample, which is [Elevation::Void] for
+///   void samples, or None if the sample is not covered by the source
+pub struct ProfileSample {
+    pub distance: f64,
+    pub position: AxisElement<f64>,
+    pub elevation: Option<Elevation>,
+}
+impl ProfileSample {
+    /// Whether or not the sample is void (or not covered by the source)
+    pub fn is_void(&self) -> bool {
+        !matches!(self.elevation, Some(Elevation::Value(_)))
+    }
+}
+
+/// Refraction coefficient of the standard atmosphere, for which the
+/// effective radius of the earth is 4/3 of [EARTH_RADIUS]
+pub const STANDARD_REFRACTION: f64 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Height of a [Viewpoint]
+pub enum Height {
+    /// Height in meters above the terrain
+    AboveGround(f64),
+    /// Altitude in meters above mean sea level
+    AboveMsl(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Point above the terrain, such as an observer or a target,
+/// see [ElevationSource::line_of_sight]
+///
+/// # Fields
+///
+/// * `position` - latitude and longitude
+/// * `height` - [Height] above the terrain or mean sea level
+pub struct Viewpoint {
+    pub position: AxisElement<f64>,
+    pub height: Height,
+}
+impl Viewpoint {
+    /// Create a [Viewpoint]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `height` - [Height] above the terrain or mean sea level
+    ///
+    /// # Returns
+    ///
+    /// * [Viewpoint]
+    pub fn new(lat: f64, lon: f64, height: Height) -> Viewpoint {
+        Viewpoint { position: AxisElement::new(lat, lon), height }
+    }
+
+    /// Altitude above mean sea level
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - [ElevationSource] of the terrain
+    ///
+    /// # Returns
+    ///
+    /// * Altitude in meters, or None if the height is [Height::AboveGround]
+    ///   and the terrain is void or not covered by the source
+    pub fn altitude<S: ElevationSource + ?Sized>(&self, source: &S) -> Option<f64> {
+        match self.height {
+            Height::AboveMsl(altitude) => Some(altitude),
+            Height::AboveGround(height) => {
+                let (lat, lon) = (self.position.lat, self.position.lon);
+                Some(source.elevation(lat, lon)?.value()? + height)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Options for [ElevationSource::line_of_sight]
+///
+/// # Fields
+///
+/// * `spacing` - [ProfileSpacing] between the samples of the terrain,
+///   defaults to [ProfileSpacing::Posts]
+/// * `curvature` - whether or not to account for the curvature of the earth,
+///   which raises the terrain between the observer and the target. Defaults to true
+/// * `refraction` - refraction coefficient of the atmosphere, which bends the
+///   sight line towards the earth, for an effective earth radius of
+///   [EARTH_RADIUS] / (1 - `refraction`). Defaults to [STANDARD_REFRACTION]
+pub struct LineOfSightOptions {
+    pub spacing: ProfileSpacing,
+    pub curvature: bool,
+    pub refraction: f64,
+}
+impl Default for LineOfSightOptions {
+    fn default() -> LineOfSightOptions {
+        LineOfSightOptions {
+            spacing: ProfileSpacing::Posts,
+            curvature: true,
+            refraction: STANDARD_REFRACTION,
+        }
+    }
+}
+impl LineOfSightOptions {
+    /// Height in meters by which the curvature of the earth (of the effective
+    /// radius) raises the terrain, between two points `d1` and `d2` meters away
+    fn bulge(&self, d1: f64, d2: f64) -> f64 {
+        match self.curvature {
+            true => earth_bulge(d1, d2, self.refraction),
+            false => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Result of [ElevationSource::line_of_sight]
+///
+/// # Fields
+///
+/// * `visible` - whether or not the target is visible from the observer
+/// * `observer` - altitude of the observer above mean sea level in meters
+/// * `target` - altitude of the target above mean sea level in meters
+/// * `distance` - great-circle distance between the observer and the target in meters
+/// * `obstruction` - the first sample (from the observer) of the terrain
+///   above the sight line, or None if the target is visible
+/// * `clearance` - smallest height in meters of the sight line above the
+///   terrain between the observer and the target, which is negative if
+///   obstructed, or infinite if there is no terrain in between
+/// * `voids` - number of samples between the observer and the target
+///   which were skipped, as they are void or not covered by the source
+pub struct LineOfSight {
+    pub visible: bool,
+    pub observer: f64,
+    pub target: f64,
+    pub distance: f64,
+    pub obstruction: Option<ProfileSample>,
+    pub clearance: f64,
+    pub voids: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Options for [DTEDData::viewshed]
+///
+/// # Fields
+///
+/// * `target_height` - height in meters above the terrain at which each post
+///   is visible, e.g. of the targets to be seen. Defaults to 0
+/// * `curvature` - whether or not to account for the curvature of the earth,
+///   see [LineOfSightOptions]. Defaults to true
+/// * `refraction` - refraction coefficient of the atmosphere,
+///   see [LineOfSightOptions]. Defaults to [STANDARD_REFRACTION]
+pub struct ViewshedOptions {
+    pub target_height: f64,
+    pub curvature: bool,
+    pub refraction: f64,
+}
+impl Default for ViewshedOptions {
+    fn default() -> ViewshedOptions {
+        ViewshedOptions {
+            target_height: 0.0,
+            curvature: true,
+            refraction: STANDARD_REFRACTION,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Result of [DTEDData::viewshed], aligned to the posts of the tile
+///
+/// # Fields
+///
+/// * `observer` - altitude of the observer above mean sea level in meters
+/// * `visible` - mask indexed as `[lon_index][lat_index]` (see [DTEDData::void_mask])
+///   where `true` marks a post visible from the observer. Posts beyond the
+///   range and void posts are not visible
+/// * `horizon` - angle in degrees above the horizontal of the horizon from the
+///   observer, i.e. of the highest terrain between the observer and each post,
+///   indexed as `[lon_index][lat_index]`. A post is visible when it is at or
+///   above the horizon, which is -90 where there is no terrain in between,
+///   and NaN for the posts beyond the range
+pub struct Viewshed {
+    pub observer: f64,
+    pub visible: Vec<Vec<bool>>,
+    pub horizon: Vec<Vec<f32>>,
+}
+impl Viewshed {
+    /// Number of visible posts
+    pub fn visible_count(&self) -> usize {
+        self.visible.iter().flatten().filter(|&&visible| visible).count()
+    }
+}
+
+impl DTEDData {
+    /// Compute the viewshed of an observer over the tile, i.e. the posts
+    /// visible from the observer within a range
+    ///
+    /// Uses an R2 sweep: rays are traced from the observer to each post on
+    /// the perimeter of the range, interpolating the terrain where each ray
+    /// crosses the lines of posts, and each post takes the visibility of the
+    /// ray passing closest to it. Distances use a local equirectangular
+    /// approximation. Void terrain does not obstruct the rays
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - [Viewpoint] of the observer, within the tile
+    /// * `range` - maximum distance from the observer in meters
+    /// * `options` - [ViewshedOptions]
+    ///
+    /// # Returns
+    ///
+    /// * [Viewshed], or None if the observer is outside of the tile, or its
+    ///   altitude is unknown (i.e. a [Height::AboveGround] over a void)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::terrain::{ Height, ViewshedOptions, Viewpoint };
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let observer = Viewpoint::new(-29.5, -177.5, Height::AboveGround(10.0));
+    /// let viewshed = data.viewshed(observer, 20_000.0, &ViewshedOptions::default()).unwrap();
+    /// assert!(viewshed.visible[60][60]);
+    /// // beyond the range
+    /// assert!(!viewshed.visible[0][0]);
+    /// assert!(viewshed.horizon[0][0].is_nan());
+    /// assert!(viewshed.visible_count() > 0);
+    /// ```
+    pub fn viewshed(
+        &self,
+        observer: Viewpoint,
+        range: f64,
+        options: &ViewshedOptions,
+    ) -> Option<Viewshed> {
+        let (lat_idx, lon_idx) = self.get_indices(observer.position.lat, observer.position.lon)?;
+        let altitude = observer.altitude(self)?;
+        let count = self.metadata.count;
+        let (lat_len, lon_len) = (count.lat as usize, count.lon as usize);
+        // --------------------------------------------------
+        // distance between the lines of posts in meters, where the
+        // longitude lines are at the latitude midway to the observer
+        // --------------------------------------------------
+        let meters_per_degree = EARTH_RADIUS * 1.0_f64.to_radians();
+        let lat_meters = self.metadata.interval.lat * meters_per_degree;
+        let lon_meters_at = |lat_idx: f64| {
+            let lat = self.min.lat + lat_idx * self.metadata.interval.lat;
+            self.metadata.interval.lon * meters_per_degree * lat.to_radians().cos()
+        };
+        let lon_meters = (0..lat_len)
+            .map(|lat| lon_meters_at((lat as f64 + lat_idx) / 2.0))
+            .collect();
+        let mut sweep = Sweep {
+            data: self,
+            origin: AxisElement::new(lat_idx, lon_idx),
+            altitude,
+            range,
+            options,
+            lat_meters,
+            lon_meters,
+            offsets: vec![vec![f64::INFINITY; lat_len]; lon_len],
+            viewshed: Viewshed {
+                observer: altitude,
+                visible: vec![vec![false; lat_len]; lon_len],
+                horizon: vec![vec![f32::NAN; lat_len]; lon_len],
+            },
+        };
+        // --------------------------------------------------
+        // the post of the observer
+        // --------------------------------------------------
+        let (lat_post, lon_post) = (lat_idx.round() as usize, lon_idx.round() as usize);
+        sweep.offsets[lon_post][lat_post] = 0.0;
+        sweep.viewshed.visible[lon_post][lat_post] = sweep.post(lat_post, lon_post).is_some();
+        sweep.viewshed.horizon[lon_post][lat_post] = -90.0;
+        // --------------------------------------------------
+        // window of the posts within range, widening the longitude
+        // reach by the latitude (of the window) furthest from the equator
+        // --------------------------------------------------
+        let range = range.max(0.0);
+        let lat_reach = range / lat_meters;
+        let lat_lo = (lat_idx - lat_reach).floor().max(0.0) as usize;
+        let lat_hi = ((lat_idx + lat_reach).ceil() as usize).min(lat_len - 1);
+        let lon_reach = range / lon_meters_at(lat_lo as f64).min(lon_meters_at(lat_hi as f64));
+        let lon_lo = (lon_idx - lon_reach).floor().max(0.0) as usize;
+        let lon_hi = ((lon_idx + lon_reach).ceil() as usize).min(lon_len - 1);
+        // --------------------------------------------------
+        // trace a ray to each post on the perimeter of the window
+        // --------------------------------------------------
+        for lon in lon_lo..=lon_hi {
+            sweep.ray(lat_lo, lon);
+            sweep.ray(lat_hi, lon);
+        }
+        for lat in lat_lo..=lat_hi {
+            sweep.ray(lat, lon_lo);
+            sweep.ray(lat, lon_hi);
+        }
+        Some(sweep.viewshed)
+    }
+}
+
+/// State of the R2 sweep of [DTEDData::viewshed]
+///
+/// # Fields
+///
+/// * `origin` - fractional indices of the observer
+/// * `lat_meters` - distance between the latitude lines of posts in meters
+/// * `lon_meters` - distance between the longitude lines of posts in meters,
+///   midway between each latitude line and the observer
+/// * `offsets` - offset (in posts) of the closest ray to each post
+///   so far, from which its visibility was taken
+struct Sweep<'a> {
+    data: &'a DTEDData,
+    origin: AxisElement<f64>,
+    altitude: f64,
+    range: f64,
+    options: &'a ViewshedOptions,
+    lat_meters: f64,
+    lon_meters: Vec<f64>,
+    offsets: Vec<Vec<f64>>,
+    viewshed: Viewshed,
+}
+impl Sweep<'_> {
+    /// Trace a ray from the observer to a post, stepping through
+    /// each line of posts along the major axis of the ray
+    fn ray(&mut self, lat: usize, lon: usize) {
+        let to = AxisElement::new(lat as f64, lon as f64);
+        let delta = to - self.origin;
+        let lat_major = delta.lat.abs() > delta.lon.abs();
+        let (origin, end) = match lat_major {
+            true => (self.origin.lat, to.lat),
+            false => (self.origin.lon, to.lon),
+        };
+        if end == origin {
+            return;
+        }
+        let step = (end - origin).signum();
+        let mut major = match step > 0.0 {
+            true => origin.floor() + 1.0,
+            false => origin.ceil() - 1.0,
+        };
+        let mut horizon = f64::NEG_INFINITY;
+        while (major - end) * step <= 0.0 {
+            // --------------------------------------------------
+            // point of the ray on this line of posts, and the closest post
+            // --------------------------------------------------
+            let point = self.origin + delta * ((major - origin) / (end - origin));
+            let (minor, post) = match lat_major {
+                true => (point.lon, AxisElement::new(major, point.lon.round())),
+                false => (point.lat, AxisElement::new(point.lat.round(), major)),
+            };
+            let (lat_post, lon_post) = (post.lat as usize, post.lon as usize);
+            let offset = (minor - minor.round()).abs();
+            let distance = self.distance(post);
+            if distance <= self.range && offset < self.offsets[lon_post][lat_post] {
+                let target = self.post(lat_post, lon_post).map(|z| z + self.options.target_height);
+                self.offsets[lon_post][lat_post] = offset;
+                self.viewshed.visible[lon_post][lat_post] =
+                    target.is_some_and(|z| self.slope(z, distance) >= horizon);
+                self.viewshed.horizon[lon_post][lat_post] = horizon.atan().to_degrees() as f32;
+            }
+            // --------------------------------------------------
+            // raise the horizon by the terrain at the point
+            // --------------------------------------------------
+            let (lo, hi) = (minor.floor() as usize, minor.ceil() as usize);
+            let frac = minor - minor.floor();
+            let (lo, hi) = match lat_major {
+                true => (self.post(major as usize, lo), self.post(major as usize, hi)),
+                false => (self.post(lo, major as usize), self.post(hi, major as usize)),
+            };
+            let terrain = match (lo, hi) {
+                (Some(lo), Some(hi)) => Some(lo + (hi - lo) * frac),
+                (lo, hi) => lo.or(hi),
+            };
+            if let Some(terrain) = terrain {
+                horizon = horizon.max(self.slope(terrain, self.distance(point)));
+            }
+            major += step;
+        }
+    }
+
+    /// Elevation of a post, or None if void
+    fn post(&self, lat: usize, lon: usize) -> Option<f64> {
+        Elevation::from_post(self.data.data[lon].elevations[lat]).value()
+    }
+
+    /// Slope from the observer to an elevation at a distance,
+    /// lowered by the curvature of the earth
+    fn slope(&self, elevation: f64, distance: f64) -> f64 {
+        let drop = match self.options.curvature {
+            true => earth_bulge(distance, distance, self.options.refraction),
+            false => 0.0,
+        };
+        (elevation - drop - self.altitude) / distance
+    }
+
+    /// Distance in meters from the observer to fractional indices
+    fn distance(&self, indices: AxisElement<f64>) -> f64 {
+        let delta = indices - self.origin;
+        let lat = delta.lat * self.lat_meters;
+        let lon = delta.lon * self.lon_meters[indices.lat.round() as usize];
+        (lat * lat + lon * lon).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Kernel estimating the gradient of the terrain at a post, from its neighbours
+pub enum SlopeKernel {
+    /// Horn (1981): weighted differences of the 3x3 neighbourhood
+    #[default]
+    Horn,
+    /// Zevenbergen and Thorne (1987): differences of the 4 adjacent posts
+    ZevenbergenThorne,
+}
+impl SlopeKernel {
+    /// Weights of the rows (or columns) of the 3x3 neighbourhood,
+    /// across the direction of the differences
+    fn weights(&self) -> [f64; 3] {
+        match self {
+            SlopeKernel::Horn => [1.0, 2.0, 1.0],
+            SlopeKernel::ZevenbergenThorne => [0.0, 1.0, 0.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Unit of a slope, see [Gradient::slope]
+pub enum SlopeUnit {
+    /// Angle from the horizontal in degrees
+    #[default]
+    Degrees,
+    /// Rise over run in percent
+    Percent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Gradient of the terrain, see [DTEDData::get_gradient]
+///
+/// # Fields
+///
+/// * `east` - rise of the terrain in meters per meter towards the east
+/// * `north` - rise of the terrain in meters per meter towards the north
+pub struct Gradient {
+    pub east: f64,
+    pub north: f64,
+}
+impl Gradient {
+    /// Steepness of the terrain
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - [SlopeUnit]
+    ///
+    /// # Returns
+    ///
+    /// * Slope in degrees or percent
+    pub fn slope(&self, unit: SlopeUnit) -> f64 {
+        let rise = self.east.hypot(self.north);
+        match unit {
+            SlopeUnit::Degrees => rise.atan().to_degrees(),
+            SlopeUnit::Percent => rise * 100.0,
+        }
+    }
+
+    /// Direction the terrain faces, i.e. of the steepest descent
+    ///
+    /// # Returns
+    ///
+    /// * Aspect in degrees clockwise from north, within [0, 360),
+    ///   or None if the terrain is flat
+    pub fn aspect(&self) -> Option<f64> {
+        if self.east == 0.0 && self.north == 0.0 {
+            return None;
+        }
+        Some((-self.east).atan2(-self.north).to_degrees().rem_euclid(360.0))
+    }
+}
+
+impl DTEDData {
+    /// Compute the slope and aspect of every post
+    ///
+    /// The gradient is estimated by the [SlopeKernel], with the distance
+    /// between the longitude lines shrinking with the latitude of each
+    /// post. Posts on the edges of the data use one-sided differences
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel` - [SlopeKernel]
+    /// * `unit` - [SlopeUnit] of the slope
+    ///
+    /// # Returns
+    ///
+    /// * Slope and aspect (see [Gradient::aspect]) of each post, indexed as
+    ///   `[lon_index][lat_index]` (see [DTEDData::void_mask]). Both are NaN
+    ///   where the post or any post within the kernel is void, and the
+    ///   aspect is NaN where the terrain is flat
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::terrain::{ SlopeKernel, SlopeUnit };
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let (slope, aspect) = data.slope_aspect(SlopeKernel::Horn, SlopeUnit::Degrees);
+    /// assert_eq!(slope.len(), data.data.len());
+    /// assert!(slope.iter().flatten().all(|&slope| (0.0..90.0).contains(&slope)));
+    /// assert!(aspect.iter().flatten().all(|&aspect| aspect.is_nan() || aspect < 360.0));
+    /// ```
+    pub fn slope_aspect(
+        &self,
+        kernel: SlopeKernel,
+        unit: SlopeUnit,
+    ) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+        let count = self.metadata.count;
+        let gradients: Vec<Vec<Option<Gradient>>> = (0..count.lon as usize)
+            .map(|lon| {
+                (0..count.lat as usize)
+                    .map(|lat| self.post_gradient(lat, lon, kernel))
+                    .collect()
+            })
+            .collect();
+        let raster = |f: &dyn Fn(&Gradient) -> Option<f64>| -> Vec<Vec<f32>> {
+            gradients
+                .iter()
+                .map(|column| {
+                    column
+                        .iter()
+                        .map(|gradient| gradient.as_ref().and_then(f))
+                        .map(|value| value.map_or(f32::NAN, |value| value as f32))
+                        .collect()
+                })
+                .collect()
+        };
+        (raster(&|gradient| Some(gradient.slope(unit))), raster(&Gradient::aspect))
+    }
+
+    /// Get the gradient of the terrain at a lat/lon, bilinearly
+    /// interpolated from the gradients of the surrounding posts
+    /// (see [DTEDData::slope_aspect])
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    /// * `kernel` - [SlopeKernel]
+    ///
+    /// # Returns
+    ///
+    /// * [Gradient], or None if out of bounds, or the gradients of the
+    ///   surrounding posts are unavailable (i.e. void)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// use dted2::terrain::{ SlopeKernel, SlopeUnit };
+    ///
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let gradient = data.get_gradient(-29.5, -177.5, SlopeKernel::Horn).unwrap();
+    /// assert_eq!(data.get_slope(-29.5, -177.5), Some(gradient.slope(SlopeUnit::Degrees)));
+    /// assert!(data.get_gradient(-28.5, -177.5, SlopeKernel::Horn).is_none());
+    /// ```
+    pub fn get_gradient<T: Into<f64>, U: Into<f64>>(
+        &self,
+        lat: T,
+        lon: U,
+        kernel: SlopeKernel,
+    ) -> Option<Gradient> {
+        let indices = self.get_indices(lat, lon)?;
+        let count = self.metadata.count;
+        let component = |f: fn(&Gradient) -> f64| {
+            dted::bilinear(count, indices, |lat_int, lon_int| {
+                self.post_gradient(lat_int, lon_int, kernel).as_ref().map(f)
+            })
+        };
+        Some(Gradient {
+            east: component(|gradient| gradient.east)?,
+            north: component(|gradient| gradient.north)?,
+        })
+    }
+
+    /// Get the slope at a lat/lon in degrees, using the [SlopeKernel::Horn]
+    /// kernel, see [DTEDData::get_gradient]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Slope in degrees, or None if out of bounds or void
+    pub fn get_slope<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+        let gradient = self.get_gradient(lat, lon, SlopeKernel::Horn)?;
+        Some(gradient.slope(SlopeUnit::Degrees))
+    }
+
+    /// Get the aspect at a lat/lon in degrees clockwise from north, using
+    /// the [SlopeKernel::Horn] kernel, see [DTEDData::get_gradient]
+    ///
+    /// # Arguments
+    ///
+    /// * `lat` - latitude
+    /// * `lon` - longitude
+    ///
+    /// # Returns
+    ///
+    /// * Aspect in degrees, or None if out of bounds, void, or flat
+    pub fn get_aspect<T: Into<f64>, U: Into<f64>>(&self, lat: T, lon: U) -> Option<f64> {
+        self.get_gradient(lat, lon, SlopeKernel::Horn)?.aspect()
+    }
+
+    /// Gradient at a post, from the differences of its neighbours weighted by
+    /// the kernel, or None if any post within the kernel is void
+    fn post_gradient(&self, lat: usize, lon: usize, kernel: SlopeKernel) -> Option<Gradient> {
+        let count = self.metadata.count;
+        let post = |lat: usize, lon: usize| {
+            Elevation::from_post(self.data[lon].elevations[lat]).value()
+        };
+        post(lat, lon)?;
+        // --------------------------------------------------
+        // neighbours, clamped to the edges of the data
+        // --------------------------------------------------
+        let (south, north) = (lat.saturating_sub(1), (lat + 1).min(count.lat as usize - 1));
+        let (west, east) = (lon.saturating_sub(1), (lon + 1).min(count.lon as usize - 1));
+        let (rows, columns) = ([south, lat, north], [west, lon, east]);
+        // --------------------------------------------------
+        // metric distance between the posts
+        // --------------------------------------------------
+        let meters_per_degree = EARTH_RADIUS * 1.0_f64.to_radians();
+        let interval = self.metadata.interval;
+        let lat_deg = self.min.lat + lat as f64 * interval.lat;
+        let dy = (north - south) as f64 * interval.lat * meters_per_degree;
+        let lon_meters = interval.lon * meters_per_degree * lat_deg.to_radians().cos();
+        let dx = (east - west) as f64 * lon_meters;
+        // --------------------------------------------------
+        // weighted differences across the kernel
+        // --------------------------------------------------
+        let weights = kernel.weights();
+        let total: f64 = weights.iter().sum();
+        let (mut d_east, mut d_north) = (0.0, 0.0);
+        for (i, &weight) in weights.iter().enumerate().filter(|(_, &weight)| weight != 0.0) {
+            d_east += weight * (post(rows[i], east)? - post(rows[i], west)?);
+            d_north += weight * (post(north, columns[i])? - post(south, columns[i])?);
+        }
+        Some(Gradient {
+            east: d_east / total / dx,
+            north: d_north / total / dy,
+        })
+    }
+}
+
+/// Height in meters by which the curvature of the earth (of the effective
+/// radius, see [LineOfSightOptions]) raises a point, `d1` and `d2` meters
+/// away from two points on the earth, above the chord between them
+fn earth_bulge(d1: f64, d2: f64, refraction: f64) -> f64 {
+    d1 * d2 * (1.0 - refraction) / (2.0 * EARTH_RADIUS)
+}
+
+/// Trace the sight line between the altitudes of the first and
+/// the last sample of a profile, over the samples in between
+fn sight_line(
+    profile: &[ProfileSample],
+    observer: f64,
+    target: f64,
+    options: &LineOfSightOptions,
+) -> LineOfSight {
+    let distance = profile.last().map_or(0.0, |sample| sample.distance);
+    let mut sight = LineOfSight {
+        visible: true,
+        observer,
+        target,
+        distance,
+        obstruction: None,
+        clearance: f64::INFINITY,
+        voids: 0,
+    };
+    let between = profile.get(1..profile.len().saturating_sub(1)).unwrap_or_default();
+    for sample in between {
+        let elevation = match sample.elevation.and_then(|elevation| elevation.value()) {
+            Some(elevation) => elevation,
+            None => {
+                sight.voids += 1;
+                continue;
+            }
+        };
+        let fraction = sample.distance / distance;
+        let line = observer + (target - observer) * fraction;
+        let terrain = elevation + options.bulge(sample.distance, distance - sample.distance);
+        let clearance = line - terrain;
+        sight.clearance = sight.clearance.min(clearance);
+        if clearance < 0.0 && sight.obstruction.is_none() {
+            sight.visible = false;
+            sight.obstruction = Some(*sample);
+        }
+    }
+    sight
+}
+
+/// Great-circle distance between two points, on a sphere of [EARTH_RADIUS]
+///
+/// # Arguments
+///
+/// * `a` - latitude and longitude of the first point
+/// * `b` - latitude and longitude of the second point
+///
+/// # Returns
+///
+/// * Distance in meters
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::AxisElement;
+/// use dted2::terrain::great_circle_distance;
+///
+/// // 1 degree along the equator
+/// let (a, b) = (AxisElement::new(0.0, 10.0), AxisElement::new(0.0, 11.0));
+/// let distance = great_circle_distance(a, b);
+/// assert!((distance - 111_195.1).abs() < 0.1);
+/// ```
+pub fn great_circle_distance(a: AxisElement<f64>, b: AxisElement<f64>) -> f64 {
+    EARTH_RADIUS * central_angle(a, b)
+}
+
+/// Point along the great circle between two points
+///
+/// # Arguments
+///
+/// * `a` - latitude and longitude of the first point
+/// * `b` - latitude and longitude of the second point
+/// * `fraction` - fraction of the distance from `a` to `b`
+///
+/// # Returns
+///
+/// * Latitude and longitude of the point, with the longitude within [-180, 180]
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::AxisElement;
+/// use dted2::terrain::great_circle_point;
+///
+/// // across the antimeridian
+/// let (a, b) = (AxisElement::new(0.0, 179.0), AxisElement::new(0.0, -179.0));
+/// let point = great_circle_point(a, b, 0.25);
+/// assert!((point.lon - 179.5).abs() < 1e-9);
+/// ```
+pub fn great_circle_point(
+    a: AxisElement<f64>,
+    b: AxisElement<f64>,
+    fraction: f64,
+) -> AxisElement<f64> {
+    let angle = central_angle(a, b);
+    if angle == 0.0 {
+        return a;
+    }
+    // --------------------------------------------------
+    // spherical linear interpolation of the unit vectors
+    // --------------------------------------------------
+    let wa = ((1.0 - fraction) * angle).sin() / angle.sin();
+    let wb = (fraction * angle).sin() / angle.sin();
+    let (va, vb) = (unit_vector(a), unit_vector(b));
+    let v: Vec<f64> = (0..3).map(|i| wa * va[i] + wb * vb[i]).collect();
+    AxisElement::new(
+        v[2].atan2(v[0].hypot(v[1])).to_degrees(),
+        v[1].atan2(v[0]).to_degrees(),
+    )
+}
+
+/// Central angle between two points (in radians), using the haversine formula
+fn central_angle(a: AxisElement<f64>, b: AxisElement<f64>) -> f64 {
+    let (lat_a, lat_b) = (a.lat.to_radians(), b.lat.to_radians());
+    let d_lat = lat_b - lat_a;
+    let d_lon = (b.lon - a.lon).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2)
+        + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * h.sqrt().min(1.0).asin()
+}
+
+/// Unit vector (earth-centered) of a lat/lon
+fn unit_vector(p: AxisElement<f64>) -> [f64; 3] {
+    let (lat, lon) = (p.lat.to_radians(), p.lon.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Distance between posts in meters, see [ProfileSpacing::Posts]
+fn post_spacing(interval: AxisElement<f64>, lat: f64) -> f64 {
+    let meters_per_degree = EARTH_RADIUS * 1.0_f64.to_radians();
+    let lat_spacing = interval.lat * meters_per_degree;
+    let lon_spacing = interval.lon * meters_per_degree * lat.to_radians().cos();
+    lat_spacing.min(lon_spacing)
+}
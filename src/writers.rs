@@ -0,0 +1,708 @@
+//! Contains serializers for the components of a DTED file, the inverse of [crate::parsers].
+//!
+//! The main entry point is [`DTEDData::write`].
+
+// --------------------------------------------------
+// external
+// --------------------------------------------------
+use std::io::Write;
+use std::path::Path;
+
+// --------------------------------------------------
+// local
+// --------------------------------------------------
+use crate::dted::*;
+use crate::parsers::{self, U16_DATA_MSK, U16_SIGN_BIT};
+use crate::primitives::{Angle, AxisElement};
+use crate::DTEDData;
+use crate::Error as DTEDError;
+
+// --------------------------------------------------
+// general constants
+// --------------------------------------------------
+/// Hemispheres of a latitude (positive, negative)
+const LAT_HEMISPHERES: [u8; 2] = [b'N', b'S'];
+/// Hemispheres of a longitude (positive, negative)
+const LON_HEMISPHERES: [u8; 2] = [b'E', b'W'];
+/// Representation of a numeric field which is not available
+const NA: &[u8] = b"NA";
+
+/// Convert an i16 to signed magnitude, the inverse of [parsers::to_i16]
+///
+/// [i16::MIN] has no signed magnitude representation, and is
+/// clamped to -32767 (see [DT2_VOID])
+///
+/// # Arguments
+///
+/// * `x` - The value to convert
+///
+/// # Returns
+///
+/// The signed magnitude int (2 bytes, formatted as u16)
+///
+/// # Examples
+///
+/// ```
+/// use dted2::parsers::to_i16;
+/// use dted2::writers::to_signed_mag;
+/// assert_eq!(to_signed_mag(3), 0x0003);
+/// assert_eq!(to_signed_mag(-3), 0x8003);
+/// assert_eq!(to_signed_mag(-32767), 0xFFFF);
+/// for x in [-32767, -1, 0, 1, 32767] {
+///     assert_eq!(to_i16(to_signed_mag(x)), x);
+/// }
+/// ```
+pub fn to_signed_mag(x: i16) -> u16 {
+    let magnitude = x.unsigned_abs().min(U16_DATA_MSK);
+    match x < 0 {
+        true => U16_SIGN_BIT | magnitude,
+        false => magnitude,
+    }
+}
+
+/// Format an unsigned integer as a zero-padded field of `width` digits,
+/// the inverse of [parsers::uint_parser]. Values which do not fit are
+/// clamped to the largest value of the field
+///
+/// # Examples
+///
+/// ```
+/// use dted2::writers::uint_field;
+/// assert_eq!(uint_field(121_u16, 4), b"0121");
+/// assert_eq!(uint_field(12345_u16, 4), b"9999");
+/// ```
+pub fn uint_field<U: Into<u64>>(value: U, width: usize) -> Vec<u8> {
+    let max = 10_u64.saturating_pow(width as u32) - 1;
+    format!("{:0width$}", value.into().min(max), width = width).into_bytes()
+}
+
+/// Format an optional unsigned integer as a field of `width` digits,
+/// see [uint_field]. If None, the field is `none`, padded with blanks
+fn opt_uint_field<U: Into<u64>>(value: Option<U>, width: usize, none: &[u8]) -> Vec<u8> {
+    match value {
+        Some(value) => uint_field(value, width),
+        None => pad(none, width),
+    }
+}
+
+/// Format text as a field of `width` bytes, left justified and padded
+/// with blanks, the inverse of [parsers::text_parser]. If None, the field is blank
+///
+/// # Examples
+///
+/// ```
+/// use dted2::writers::text_field;
+/// assert_eq!(text_field(Some("MSL"), 5), b"MSL  ");
+/// assert_eq!(text_field(Some("WGS84"), 3), b"WGS");
+/// assert_eq!(text_field(None, 2), b"  ");
+/// ```
+pub fn text_field(text: Option<&str>, width: usize) -> Vec<u8> {
+    pad(text.unwrap_or_default().as_bytes(), width)
+}
+
+/// Truncate or pad (with blanks) bytes to `width` bytes
+fn pad(bytes: &[u8], width: usize) -> Vec<u8> {
+    let mut field = bytes[..bytes.len().min(width)].to_vec();
+    field.resize(width, b' ');
+    field
+}
+
+/// Format an [Angle] as a field, the inverse of [parsers::angle_parser]
+/// and [parsers::decimal_angle_parser]
+///
+/// # Arguments
+///
+/// * `angle` - The angle to format
+/// * `num_deg` - The number of digits for degrees
+/// * `num_min` - The number of digits for minutes
+/// * `num_sec` - The number of digits for (integer) seconds
+/// * `num_frac` - The number of digits for fractional seconds, 0 for no decimal point
+/// * `hemispheres` - The hemisphere characters (positive, negative), None for no hemisphere
+///
+/// # Examples
+///
+/// ```
+/// use dted2::primitives::Angle;
+/// use dted2::writers::angle_field;
+/// assert_eq!(angle_field(Angle::new(178, 0, 0.0, true), 3, 2, 2, 0, Some([b'E', b'W'])), b"1780000W");
+/// assert_eq!(angle_field(Angle::new(30, 0, 0.0, true), 2, 2, 2, 1, Some([b'N', b'S'])), b"300000.0S");
+/// assert_eq!(angle_field(Angle::new(12, 34, 59.96, false), 3, 2, 2, 1, None), b"0123500.0");
+/// ```
+pub fn angle_field(
+    angle: Angle,
+    num_deg: usize,
+    num_min: usize,
+    num_sec: usize,
+    num_frac: usize,
+    hemispheres: Option<[u8; 2]>,
+) -> Vec<u8> {
+    // --------------------------------------------------
+    // round to the precision of the field, carrying
+    // into the seconds, minutes and degrees
+    // --------------------------------------------------
+    let scale = 10_u64.pow(num_frac as u32);
+    let total = (angle.total_secs().abs() * scale as f64).round() as u64;
+    let (secs, frac) = (total / scale, total % scale);
+    let mut field = format!(
+        "{:0deg$}{:0min$}{:0sec$}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+        deg = num_deg,
+        min = num_min,
+        sec = num_sec,
+    );
+    if num_frac > 0 {
+        field.push_str(&format!(".{:0frac$}", frac, frac = num_frac));
+    }
+    let mut field = field.into_bytes();
+    if let Some(hemispheres) = hemispheres {
+        field.push(hemispheres[angle.is_negative() as usize]);
+    }
+    field
+}
+
+/// A header record being serialized, starting from a template
+/// (the record as read) such that reserved fields, and fields
+/// which have not been modified, are preserved byte-for-byte
+struct RecordWriter {
+    bytes: Vec<u8>,
+}
+impl RecordWriter {
+    /// Start a record of `len` bytes from a template, or
+    /// from a blank record beginning with the sentinel
+    fn new(template: Option<&[u8]>, sentinel: RecognitionSentinel, len: usize) -> Self {
+        let bytes = match template {
+            Some(template) => template.to_vec(),
+            None => {
+                let mut bytes = pad(b"", len);
+                let sentinel = sentinel.value();
+                bytes[..sentinel.len()].copy_from_slice(sentinel);
+                bytes
+            }
+        };
+        RecordWriter { bytes }
+    }
+
+    /// Write a field at `offset`, unless it is unchanged from the template
+    fn field(&mut self, offset: usize, unchanged: bool, field: Vec<u8>) {
+        if !unchanged {
+            self.bytes[offset..offset + field.len()].copy_from_slice(&field);
+        }
+    }
+}
+
+/// Slice of the template bytes of a header record, if fully present
+fn template(metadata: &DTEDMetadata, start: usize, len: usize) -> Option<&[u8]> {
+    metadata.header_bytes.as_ref()?.get(start..start + len)
+}
+
+/// Serialize the User Header Label (UHL) of a [DTEDMetadata]
+///
+/// # Arguments
+///
+/// * `metadata` - [DTEDMetadata]
+///
+/// # Returns
+///
+/// The [DT2_UHL_LENGTH] bytes of the UHL
+///
+/// # Examples
+///
+/// ```
+/// use dted2::DTEDData;
+/// use dted2::writers::dted_uhl_bytes;
+///
+/// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let mut metadata = DTEDData::header_from_bytes(&raw).unwrap();
+/// assert_eq!(dted_uhl_bytes(&metadata), &raw[..80]);
+/// // without the bytes as read, the accuracy is written as `NA  ` rather than `00NA`
+/// metadata.clear_header_bytes();
+/// assert_eq!(&dted_uhl_bytes(&metadata)[28..32], b"NA  ");
+/// ```
+pub fn dted_uhl_bytes(metadata: &DTEDMetadata) -> Vec<u8> {
+    let uhl = RawDTEDHeader {
+        origin: metadata.origin_angle,
+        interval_secs_x_10: interval_secs_x_10(metadata),
+        accuracy: metadata.accuracy,
+        security_code: metadata.security_code,
+        reference: metadata.reference.clone(),
+        count: metadata.count,
+        multiple_accuracy: metadata.multiple_accuracy,
+    };
+    // --------------------------------------------------
+    // start from the UHL as read, if any
+    // --------------------------------------------------
+    let template = template(metadata, 0, DT2_UHL_LENGTH as usize)
+        .and_then(|bytes| Some((bytes, parsers::dted_uhl_parser(bytes).ok()?.1)));
+    let mut record = RecordWriter::new(
+        template.as_ref().map(|(bytes, _)| *bytes),
+        RecognitionSentinel::UHL,
+        DT2_UHL_LENGTH as usize,
+    );
+    let t = template.as_ref().map(|(_, t)| t);
+    macro_rules! unchanged {
+        ($($field:ident).+) => {
+            t.is_some_and(|t| t.$($field).+ == uhl.$($field).+)
+        };
+    }
+    // --------------------------------------------------
+    // write fields
+    // --------------------------------------------------
+    let security_code = uhl.security_code.map(|code| [*code.value()]);
+    record.field(
+        4,
+        unchanged!(origin.lon),
+        angle_field(uhl.origin.lon, 3, 2, 2, 0, Some(LON_HEMISPHERES)),
+    );
+    record.field(
+        12,
+        unchanged!(origin.lat),
+        angle_field(uhl.origin.lat, 3, 2, 2, 0, Some(LAT_HEMISPHERES)),
+    );
+    record.field(
+        20,
+        unchanged!(interval_secs_x_10.lon),
+        uint_field(uhl.interval_secs_x_10.lon, 4),
+    );
+    record.field(
+        24,
+        unchanged!(interval_secs_x_10.lat),
+        uint_field(uhl.interval_secs_x_10.lat, 4),
+    );
+    record.field(
+        28,
+        unchanged!(accuracy),
+        opt_uint_field(uhl.accuracy, 4, NA),
+    );
+    record.field(
+        32,
+        unchanged!(security_code),
+        pad(security_code.as_ref().map_or(&b""[..], |c| &c[..]), 3),
+    );
+    record.field(
+        35,
+        unchanged!(reference),
+        text_field(uhl.reference.as_deref(), 12),
+    );
+    record.field(47, unchanged!(count.lon), uint_field(uhl.count.lon, 4));
+    record.field(51, unchanged!(count.lat), uint_field(uhl.count.lat, 4));
+    record.field(
+        55,
+        unchanged!(multiple_accuracy),
+        uint_field(uhl.multiple_accuracy as u8, 1),
+    );
+    record.bytes
+}
+
+/// Serialize the Data Set Identification (DSI) record of a [DTEDMetadata]
+///
+/// If [DTEDMetadata::dsi] is None, a record is derived from the metadata (see
+/// [DTEDRecordDSI::from_metadata]). The origin, corners, interval and count
+/// are always written from the metadata, such that they agree with the UHL
+///
+/// # Arguments
+///
+/// * `metadata` - [DTEDMetadata]
+///
+/// # Returns
+///
+/// The [DT2_DSI_RECORD_LENGTH] bytes of the DSI record
+pub fn dted_dsi_bytes(metadata: &DTEDMetadata) -> Vec<u8> {
+    let mut dsi = metadata
+        .dsi
+        .clone()
+        .unwrap_or_else(|| DTEDRecordDSI::from_metadata(metadata));
+    // --------------------------------------------------
+    // the geographic extent describes the data as written,
+    // e.g. a window rather than the entire cell
+    // --------------------------------------------------
+    let extent = DTEDRecordDSI::from_metadata(metadata);
+    dsi.origin = extent.origin;
+    dsi.sw_corner = extent.sw_corner;
+    dsi.nw_corner = extent.nw_corner;
+    dsi.ne_corner = extent.ne_corner;
+    dsi.se_corner = extent.se_corner;
+    dsi.interval_secs_x_10 = interval_secs_x_10(metadata);
+    dsi.count = metadata.count;
+    // --------------------------------------------------
+    // start from the DSI record as read, if any
+    // --------------------------------------------------
+    let start = DT2_UHL_LENGTH as usize;
+    let template = template(metadata, start, DT2_DSI_RECORD_LENGTH)
+        .and_then(|bytes| Some((bytes, parsers::dted_dsi_parser(bytes).ok()?.1)));
+    let mut record = RecordWriter::new(
+        template.as_ref().map(|(bytes, _)| *bytes),
+        RecognitionSentinel::DSI,
+        DT2_DSI_RECORD_LENGTH,
+    );
+    let t = template.as_ref().map(|(_, t)| t);
+    macro_rules! unchanged {
+        ($($field:ident).+) => {
+            t.is_some_and(|t| t.$($field).+ == dsi.$($field).+)
+        };
+    }
+    let lat = |angle: Angle| angle_field(angle, 2, 2, 2, 0, Some(LAT_HEMISPHERES));
+    let lon = |angle: Angle| angle_field(angle, 3, 2, 2, 0, Some(LON_HEMISPHERES));
+    // --------------------------------------------------
+    // security + identification
+    // --------------------------------------------------
+    let mm_version = dsi.mm_version.map(|c| c.to_string());
+    let security_code = dsi.security_code.map(|code| [*code.value()]);
+    record.field(
+        3,
+        unchanged!(security_code),
+        pad(security_code.as_ref().map_or(&b""[..], |c| &c[..]), 1),
+    );
+    record.field(
+        4,
+        unchanged!(security_release),
+        text_field(dsi.security_release.as_deref(), 2),
+    );
+    record.field(
+        6,
+        unchanged!(security_handling),
+        text_field(dsi.security_handling.as_deref(), 27),
+    );
+    record.field(
+        59,
+        unchanged!(version),
+        text_field(dsi.version.as_deref(), 5),
+    );
+    record.field(
+        64,
+        unchanged!(reference),
+        text_field(dsi.reference.as_deref(), 15),
+    );
+    record.field(87, unchanged!(edition), opt_uint_field(dsi.edition, 2, b""));
+    record.field(
+        89,
+        unchanged!(mm_version),
+        text_field(mm_version.as_deref(), 1),
+    );
+    record.field(
+        90,
+        unchanged!(maintenance_date),
+        opt_uint_field(dsi.maintenance_date, 4, b""),
+    );
+    record.field(94, unchanged!(mm_date), opt_uint_field(dsi.mm_date, 4, b""));
+    record.field(
+        98,
+        unchanged!(maintenance_code),
+        opt_uint_field(dsi.maintenance_code, 4, b""),
+    );
+    record.field(
+        102,
+        unchanged!(producer_code),
+        text_field(dsi.producer_code.as_deref(), 8),
+    );
+    // --------------------------------------------------
+    // product specification
+    // --------------------------------------------------
+    record.field(
+        126,
+        unchanged!(product_specs_desc),
+        text_field(dsi.product_specs_desc.as_deref(), 9),
+    );
+    record.field(
+        135,
+        unchanged!(product_specs_code),
+        opt_uint_field(dsi.product_specs_code, 2, b""),
+    );
+    record.field(
+        137,
+        unchanged!(product_specs_date),
+        opt_uint_field(dsi.product_specs_date, 4, b""),
+    );
+    record.field(
+        141,
+        unchanged!(vertical_datum),
+        text_field(dsi.vertical_datum.as_deref(), 3),
+    );
+    record.field(
+        144,
+        unchanged!(horizontal_datum),
+        text_field(dsi.horizontal_datum.as_deref(), 5),
+    );
+    record.field(
+        149,
+        unchanged!(collection_system),
+        text_field(dsi.collection_system.as_deref(), 10),
+    );
+    record.field(
+        159,
+        unchanged!(compilation_date),
+        opt_uint_field(dsi.compilation_date, 4, b""),
+    );
+    // --------------------------------------------------
+    // geographic extent
+    // --------------------------------------------------
+    record.field(
+        185,
+        unchanged!(origin.lat),
+        angle_field(dsi.origin.lat, 2, 2, 2, 1, Some(LAT_HEMISPHERES)),
+    );
+    record.field(
+        194,
+        unchanged!(origin.lon),
+        angle_field(dsi.origin.lon, 3, 2, 2, 1, Some(LON_HEMISPHERES)),
+    );
+    record.field(204, unchanged!(sw_corner.lat), lat(dsi.sw_corner.lat));
+    record.field(211, unchanged!(sw_corner.lon), lon(dsi.sw_corner.lon));
+    record.field(219, unchanged!(nw_corner.lat), lat(dsi.nw_corner.lat));
+    record.field(226, unchanged!(nw_corner.lon), lon(dsi.nw_corner.lon));
+    record.field(234, unchanged!(ne_corner.lat), lat(dsi.ne_corner.lat));
+    record.field(241, unchanged!(ne_corner.lon), lon(dsi.ne_corner.lon));
+    record.field(249, unchanged!(se_corner.lat), lat(dsi.se_corner.lat));
+    record.field(256, unchanged!(se_corner.lon), lon(dsi.se_corner.lon));
+    record.field(
+        264,
+        unchanged!(clockwise_orientation),
+        angle_field(dsi.clockwise_orientation, 3, 2, 2, 1, None),
+    );
+    record.field(
+        273,
+        unchanged!(interval_secs_x_10.lat),
+        uint_field(dsi.interval_secs_x_10.lat, 4),
+    );
+    record.field(
+        277,
+        unchanged!(interval_secs_x_10.lon),
+        uint_field(dsi.interval_secs_x_10.lon, 4),
+    );
+    record.field(281, unchanged!(count.lat), uint_field(dsi.count.lat, 4));
+    record.field(285, unchanged!(count.lon), uint_field(dsi.count.lon, 4));
+    record.field(
+        289,
+        unchanged!(partial_cell_flag),
+        uint_field(dsi.partial_cell_flag, 2),
+    );
+    record.bytes
+}
+
+/// Serialize the Accuracy Description (ACC) record of a [DTEDMetadata]
+///
+/// If [DTEDMetadata::acc] is None, a record containing only the
+/// absolute vertical accuracy of the UHL is written
+///
+/// # Arguments
+///
+/// * `metadata` - [DTEDMetadata]
+///
+/// # Returns
+///
+/// The [DT2_ACC_RECORD_LENGTH] bytes of the ACC record
+pub fn dted_acc_bytes(metadata: &DTEDMetadata) -> Vec<u8> {
+    let acc = metadata.acc.clone().unwrap_or(DTEDRecordACC {
+        accuracy: DTEDAccuracy {
+            abs_vertical: metadata.accuracy,
+            ..Default::default()
+        },
+        multiple_accuracy_flag: 0,
+        sub_regions: Vec::new(),
+    });
+    // --------------------------------------------------
+    // start from the ACC record as read, if any
+    // --------------------------------------------------
+    let start = DT2_UHL_LENGTH as usize + DT2_DSI_RECORD_LENGTH;
+    let template = template(metadata, start, DT2_ACC_RECORD_LENGTH)
+        .and_then(|bytes| Some((bytes, parsers::dted_acc_parser(bytes).ok()?.1)));
+    let mut record = RecordWriter::new(
+        template.as_ref().map(|(bytes, _)| *bytes),
+        RecognitionSentinel::ACC,
+        DT2_ACC_RECORD_LENGTH,
+    );
+    let t = template.as_ref().map(|(_, t)| t);
+    // --------------------------------------------------
+    // accuracy of the entire cell
+    // --------------------------------------------------
+    let flag = match acc.sub_regions.len() {
+        0 => acc.multiple_accuracy_flag,
+        len => len.min(DT2_ACC_MAX_SUB_REGIONS) as u8,
+    };
+    record.field(
+        3,
+        t.is_some_and(|t| t.accuracy == acc.accuracy),
+        accuracy_field(&acc.accuracy),
+    );
+    record.field(
+        55,
+        t.is_some_and(|t| t.multiple_accuracy_flag == flag),
+        uint_field(flag, 2),
+    );
+    // --------------------------------------------------
+    // accuracy sub-regions, and blanks for the unused sub-regions
+    // --------------------------------------------------
+    let mut sub_regions = Vec::with_capacity(DT2_ACC_MAX_SUB_REGIONS * DT2_ACC_SUB_REGION_LENGTH);
+    for region in acc.sub_regions.iter().take(DT2_ACC_MAX_SUB_REGIONS) {
+        let num_coords = region.outline.len().min(DT2_ACC_MAX_OUTLINE_COORDS);
+        let mut bytes = accuracy_field(&region.accuracy);
+        bytes.extend(uint_field(num_coords as u8, 2));
+        for coord in region.outline.iter().take(num_coords) {
+            bytes.extend(angle_field(coord.lat, 2, 2, 2, 1, Some(LAT_HEMISPHERES)));
+            bytes.extend(angle_field(coord.lon, 3, 2, 2, 1, Some(LON_HEMISPHERES)));
+        }
+        sub_regions.extend(pad(&bytes, DT2_ACC_SUB_REGION_LENGTH));
+    }
+    let sub_regions = pad(
+        &sub_regions,
+        DT2_ACC_MAX_SUB_REGIONS * DT2_ACC_SUB_REGION_LENGTH,
+    );
+    record.field(
+        57,
+        t.is_some_and(|t| t.sub_regions == acc.sub_regions),
+        sub_regions,
+    );
+    record.bytes
+}
+
+/// Format a [DTEDAccuracy] as four 4-byte fields, the inverse of [parsers::accuracy_parser]
+fn accuracy_field(accuracy: &DTEDAccuracy) -> Vec<u8> {
+    [
+        accuracy.abs_horizontal,
+        accuracy.abs_vertical,
+        accuracy.rel_horizontal,
+        accuracy.rel_vertical,
+    ]
+    .iter()
+    .flat_map(|value| opt_uint_field(*value, 4, NA))
+    .collect()
+}
+
+/// Data interval of the metadata in tenths of arc seconds
+fn interval_secs_x_10(metadata: &DTEDMetadata) -> AxisElement<u16> {
+    AxisElement::new(
+        (metadata.interval_secs.lat * 10.0).round() as u16,
+        (metadata.interval_secs.lon * 10.0).round() as u16,
+    )
+}
+
+/// Serialize a data record (longitude line), the inverse of [parsers::parse_dted_record]
+///
+/// # Arguments
+///
+/// * `column` - index of the longitude line, written as the block and longitude count
+/// * `elevations` - elevations of the longitude line, from south to north
+/// * `record` - buffer the record is written to, replacing its contents
+///
+/// # Examples
+///
+/// ```
+/// use dted2::dted::DT2_HEADER_LENGTH;
+/// use dted2::DTEDData;
+/// use dted2::writers::dted_record_bytes;
+///
+/// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+/// let data = DTEDData::from_bytes(&raw).unwrap();
+/// let mut record = Vec::new();
+/// dted_record_bytes(3, &data.data[3].elevations, &mut record);
+/// assert_eq!(record, &raw[DT2_HEADER_LENGTH + 3 * record.len()..][..record.len()]);
+/// ```
+pub fn dted_record_bytes(column: usize, elevations: &[i16], record: &mut Vec<u8>) {
+    record.clear();
+    record.extend_from_slice(RecognitionSentinel::DATA.value());
+    record.extend_from_slice(&(column as u32).to_be_bytes()[1..]);
+    record.extend_from_slice(&(column as u16).to_be_bytes());
+    record.extend_from_slice(&0_u16.to_be_bytes());
+    for &elevation in elevations {
+        record.extend_from_slice(&to_signed_mag(elevation).to_be_bytes());
+    }
+    let checksum = parsers::dted_record_checksum(record);
+    record.extend_from_slice(&checksum.to_be_bytes());
+}
+
+impl DTEDData {
+    /// Write the DTED data to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: Path to the DTED file
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an error if the file cannot be written
+    ///   or the data does not match [DTEDMetadata::count]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    /// let path = std::env::temp_dir().join("dted2_doc_write.dt2");
+    /// data.write(&path).unwrap();
+    /// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// assert_eq!(std::fs::read(&path).unwrap(), raw);
+    /// ```
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), DTEDError> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the DTED data to any [Write] destination
+    ///
+    /// The UHL, DSI and ACC records are written from [DTEDData::metadata]
+    /// (see [dted_uhl_bytes], [dted_dsi_bytes] and [dted_acc_bytes]), followed
+    /// by a data record for each longitude line with its checksum
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - [Write] destination
+    ///
+    /// # Returns
+    ///
+    /// * Nothing, or an error if writing fails or the
+    ///   data does not match [DTEDMetadata::count]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dted2::DTEDData;
+    /// let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    /// let mut data = DTEDData::from_bytes(&raw).unwrap();
+    /// data.data[7].elevations[11] = 1234;
+    /// let mut bytes = Vec::new();
+    /// data.write_to(&mut bytes).unwrap();
+    /// assert_eq!(bytes.len(), raw.len());
+    /// let data = DTEDData::from_bytes(&bytes).unwrap();
+    /// assert_eq!(data.data[7].elevations[11], 1234);
+    /// assert!(data.invalid_checksums.is_empty());
+    /// ```
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), DTEDError> {
+        // --------------------------------------------------
+        // verify the dimensions of the data
+        // --------------------------------------------------
+        let count = self.metadata.count;
+        if self.data.len() != count.lon as usize {
+            return Err(DTEDError::DimensionMismatch {
+                axis: "longitude lines",
+                expected: count.lon as usize,
+                found: self.data.len(),
+            });
+        }
+        if let Some(record) = self
+            .data
+            .iter()
+            .find(|r| r.elevations.len() != count.lat as usize)
+        {
+            return Err(DTEDError::DimensionMismatch {
+                axis: "latitude points",
+                expected: count.lat as usize,
+                found: record.elevations.len(),
+            });
+        }
+        // --------------------------------------------------
+        // write the header records, then the data records
+        // --------------------------------------------------
+        writer.write_all(&dted_uhl_bytes(&self.metadata))?;
+        writer.write_all(&dted_dsi_bytes(&self.metadata))?;
+        writer.write_all(&dted_acc_bytes(&self.metadata))?;
+        let mut record = Vec::with_capacity(DT2_RECORD_OVERHEAD_LENGTH + 2 * count.lat as usize);
+        for (column, data) in self.data.iter().enumerate() {
+            dted_record_bytes(column, &data.elevations, &mut record);
+            writer.write_all(&record)?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
+use dted2::dted::SecurityCode;
 use dted2::DTEDData;
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_input_data() {
     let data = DTEDData::read("tests/test_data.dt2").unwrap();
     assert_eq!(data.metadata.origin_angle.lat.is_negative(), false);
@@ -20,6 +22,7 @@ fn test_input_data() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_input_data_negative() {
     let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
     assert_eq!(data.metadata.origin_angle.lat.is_negative(), true);
@@ -54,3 +57,1483 @@ fn test_read_header_only() {
     assert_eq!(header.count.lat, 3601);
     assert_eq!(header.count.lon, 3601);
 }
+
+#[test]
+fn test_dsi_record() {
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    let header = DTEDData::read_header("tests/test_data_negative.dt2").unwrap();
+    let dsi = data.metadata.dsi.unwrap();
+    assert_eq!(Some(&dsi), header.dsi.as_ref());
+    assert_eq!(dsi.version.as_deref(), Some("DTED0"));
+    assert_eq!(dsi.producer_code.as_deref(), Some("USNIM"));
+    assert_eq!(dsi.product_specs_desc.as_deref(), Some("SPECDTED"));
+    assert_eq!(dsi.horizontal_datum.as_deref(), Some("WGS84"));
+    assert_eq!(dsi.compilation_date, None);
+    assert_eq!(dsi.origin, data.metadata.origin_angle);
+    assert_eq!(dsi.sw_corner, data.metadata.origin_angle);
+    assert_eq!(dsi.clockwise_orientation.total_secs(), 0.0);
+    assert_eq!(dsi.count, data.metadata.count);
+    assert_eq!(dsi.partial_cell_flag, 0);
+}
+
+#[test]
+fn test_numeric_fields_padded_with_blanks() {
+    let mut raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    // UHL absolute vertical accuracy, and DSI edition
+    raw[28..32].copy_from_slice(b"  30");
+    raw[80 + 87..80 + 89].copy_from_slice(b" 7");
+    let metadata = DTEDData::header_from_bytes(&raw).unwrap();
+    assert_eq!(metadata.accuracy, Some(30));
+    assert_eq!(metadata.dsi.unwrap().edition, Some(7));
+    // blanks within a field are still an error
+    raw[28..32].copy_from_slice(b"3 0 ");
+    assert!(DTEDData::header_from_bytes(&raw).is_err());
+}
+
+#[test]
+fn test_dsi_security_code() {
+    use dted2::dted::DTEDRecordDSI;
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let dsi = DTEDData::from_bytes(&raw).unwrap().metadata.dsi.unwrap();
+    assert_eq!(dsi.security_code, Some(SecurityCode::Unclassified));
+
+    // classified records are read, and written back
+    for (code, expected) in [
+        (b'S', SecurityCode::Secret),
+        (b'C', SecurityCode::Confidential),
+        (b'R', SecurityCode::Restricted),
+    ] {
+        let mut classified = raw.clone();
+        classified[83] = code;
+        let data = DTEDData::from_bytes(&classified).unwrap();
+        assert_eq!(data.metadata.dsi.as_ref().unwrap().security_code, Some(expected));
+        let mut written = Vec::new();
+        data.write_to(&mut written).unwrap();
+        assert_eq!(written, classified);
+        // and rewritten when modified
+        let mut edited = data.clone();
+        edited.metadata.dsi.as_mut().unwrap().security_code = Some(SecurityCode::Unclassified);
+        let mut written = Vec::new();
+        edited.write_to(&mut written).unwrap();
+        assert_eq!(&written[80..84], b"DSIU");
+    }
+
+    // unknown classifications are errors
+    let mut unknown = raw.clone();
+    unknown[83] = b'X';
+    assert!(DTEDData::from_bytes(&unknown).is_err());
+
+    // derived records are unclassified, unless the UHL is classified
+    let mut metadata = DTEDData::header_from_bytes(&raw).unwrap();
+    metadata.security_code = None;
+    let dsi = DTEDRecordDSI::from_metadata(&metadata);
+    assert_eq!(dsi.security_code, Some(SecurityCode::Unclassified));
+    metadata.security_code = Some(SecurityCode::Secret);
+    let dsi = DTEDRecordDSI::from_metadata(&metadata);
+    assert_eq!(dsi.security_code, Some(SecurityCode::Secret));
+}
+
+#[test]
+fn test_acc_sub_regions() {
+    use dted2::dted::DT2_ACC_RECORD_LENGTH;
+    use dted2::parsers::dted_acc_parser;
+
+    fn sub_region(accuracy: &str, outline: &[&str]) -> String {
+        let coords: String = outline.concat();
+        format!("{}{:02}{:<266}", accuracy, outline.len(), coords)
+    }
+    let mut raw = format!("ACC0020001500100005{:36}02", "");
+    // western half of the cell
+    raw += &sub_region(
+        "00300025NA  0010",
+        &["300000.0S1780000.0W", "290000.0S1780000.0W", "290000.0S1773000.0W", "300000.0S1773000.0W"],
+    );
+    // eastern half of the cell
+    raw += &sub_region(
+        "004000350025NA  ",
+        &["300000.0S1773000.0W", "290000.0S1773000.0W", "290000.0S1770000.0W"],
+    );
+    let raw = format!("{:<width$}", raw, width = DT2_ACC_RECORD_LENGTH);
+
+    let (rest, acc) = dted_acc_parser(raw.as_bytes()).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(acc.accuracy.abs_vertical, Some(15));
+    assert_eq!(acc.multiple_accuracy_flag, 2);
+    assert_eq!(acc.sub_regions.len(), 2);
+    assert_eq!(acc.sub_regions[0].outline.len(), 4);
+    assert_eq!(acc.sub_regions[0].accuracy.rel_horizontal, None);
+    assert_eq!(acc.sub_regions[1].outline.len(), 3);
+    assert_eq!(acc.accuracy_at(-29.5, -177.75).abs_vertical, Some(25));
+    assert_eq!(acc.accuracy_at(-29.1, -177.1).abs_vertical, Some(35));
+    // outside of the eastern triangle, within the cell
+    assert_eq!(acc.accuracy_at(-29.9, -177.1).abs_vertical, Some(15));
+}
+
+#[test]
+fn test_uhl_fields() {
+    let header = DTEDData::read_header("tests/test_data_negative.dt2").unwrap();
+    assert_eq!(header.security_code, Some(SecurityCode::Unclassified));
+    assert_eq!(header.reference, None);
+    assert!(!header.multiple_accuracy);
+    assert_eq!(header.acc.unwrap().sub_regions.len(), 0);
+}
+
+#[test]
+fn test_checksum_verification() {
+    use dted2::dted::{ChecksumMode, DTEDReadOptions, DT2_HEADER_LENGTH};
+    // corrupt an elevation in column 5
+    let mut raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    raw[DT2_HEADER_LENGTH + 5 * (12 + 2 * 121) + 20] ^= 0x01;
+    let path = std::env::temp_dir().join("dted2_test_checksum.dt2");
+    std::fs::write(&path, &raw).unwrap();
+    let path = path.to_str().unwrap();
+
+    let read = |checksum| DTEDData::read_with_options(path, &DTEDReadOptions { checksum, ..Default::default() });
+    assert!(read(ChecksumMode::Ignore).unwrap().invalid_checksums.is_empty());
+    assert_eq!(read(ChecksumMode::Warn).unwrap().invalid_checksums, vec![5]);
+    assert!(matches!(
+        read(ChecksumMode::Fail),
+        Err(dted2::Error::ChecksumMismatch { section: dted2::Section::Data(5), .. })
+    ));
+    assert!(DTEDData::read("tests/test_data_negative.dt2").unwrap().invalid_checksums.is_empty());
+}
+
+#[test]
+fn test_error_context() {
+    use dted2::{Error, Section};
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let path = std::env::temp_dir().join("dted2_test_error_context.dt2");
+    let read = |bytes: &[u8]| {
+        std::fs::write(&path, bytes).unwrap();
+        DTEDData::read(path.to_str().unwrap()).err().unwrap()
+    };
+    // truncated within column 7
+    let err = read(&raw[..3428 + 7 * 254 + 100]);
+    assert!(matches!(err, Error::Truncated { section: Section::Data(7), .. }));
+    // bad DSI sentinel
+    let mut bad = raw.clone();
+    bad[80] = b'X';
+    let err = read(&bad);
+    assert!(matches!(err, Error::BadSentinel { section: Section::DSI, offset: 80 }));
+    assert_eq!(err.to_string(), "Bad recognition sentinel for DSI record at byte 80");
+    // bad numeric field in the UHL longitude interval
+    let mut bad = raw.clone();
+    bad[21] = b'x';
+    let err = read(&bad);
+    assert!(matches!(err, Error::BadNumericField { section: Section::UHL, offset: 20 }));
+    // DSI longitude count does not match UHL
+    let mut bad = raw.clone();
+    bad[80 + 288] = b'2';
+    let err = read(&bad);
+    assert!(matches!(err, Error::CountMismatch { section: Section::DSI, expected: 121, found: 122, .. }));
+    assert_eq!(err.offset(), Some(80 + 285));
+}
+
+#[test]
+fn test_read_sources() {
+    use std::io::{Cursor, Read};
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let from_path = DTEDData::read(std::path::Path::new("tests/test_data_negative.dt2")).unwrap();
+    assert_eq!(from_path.metadata.filename.as_deref(), Some("tests/test_data_negative.dt2"));
+    let from_bytes = DTEDData::from_bytes(&raw).unwrap();
+    assert_eq!(from_bytes.metadata.filename, None);
+    // a stream containing the tile followed by unrelated data
+    let mut stream = Cursor::new([raw.clone(), b"trailing".to_vec()].concat());
+    let from_reader = DTEDData::from_reader(&mut stream).unwrap();
+    let mut trailing = String::new();
+    stream.read_to_string(&mut trailing).unwrap();
+    assert_eq!(trailing, "trailing");
+    let from_seekable = DTEDData::from_seekable(&mut stream).unwrap();
+    for data in [&from_bytes, &from_reader, &from_seekable] {
+        assert_eq!(data.metadata.count, from_path.metadata.count);
+        assert_eq!(data.get_elevation(-29.5, -177.5), from_path.get_elevation(-29.5, -177.5));
+    }
+    // truncated streams report the offset where data ran out
+    let err = DTEDData::from_reader(&raw[..1000]).err().unwrap();
+    assert!(matches!(err, dted2::Error::Truncated { section: dted2::Section::ACC, .. }));
+}
+
+#[test]
+fn test_header_reads_only_header_bytes() {
+    use std::io::{Cursor, Seek};
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let mut cursor = Cursor::new(&raw);
+    let header = DTEDData::header_from_reader(&mut cursor).unwrap();
+    assert_eq!(cursor.stream_position().unwrap(), 3428);
+    assert!(header.dsi.is_some() && header.acc.is_some());
+    let mut cursor = Cursor::new(&raw);
+    let uhl = DTEDData::uhl_from_reader(&mut cursor).unwrap();
+    assert_eq!(cursor.stream_position().unwrap(), 80);
+    assert!(uhl.dsi.is_none() && uhl.acc.is_none());
+    assert_eq!(uhl.count, header.count);
+    assert_eq!(uhl.origin, header.origin);
+}
+
+#[test]
+fn test_window_read() {
+    use dted2::dted::{DTEDReadOptions, DTEDWindow, DT2_HEADER_LENGTH};
+    use dted2::primitives::AxisElement;
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let full = DTEDData::from_bytes(&raw).unwrap();
+    let options = |window| DTEDReadOptions { window: Some(window), ..Default::default() };
+
+    // index window matches the full read, from every source
+    let indices = options(DTEDWindow::Indices {
+        start: AxisElement::new(10, 40),
+        end: AxisElement::new(30, 49),
+    });
+    let windows = [
+        DTEDData::from_bytes_with_options(&raw, &indices).unwrap(),
+        DTEDData::from_reader_with_options(&raw[..], &indices).unwrap(),
+        DTEDData::read_with_options("tests/test_data_negative.dt2", &indices).unwrap(),
+    ];
+    for window in windows.iter() {
+        assert_eq!(window.metadata.count, AxisElement::new(21, 10));
+        assert_eq!(window.data.len(), 10);
+        for (lon, record) in window.data.iter().enumerate() {
+            assert_eq!(record.elevations, full.data[40 + lon].elevations[10..31]);
+        }
+        assert_eq!(window.min, full.min + AxisElement::new(10.0, 40.0) * full.metadata.interval);
+        assert_eq!(window.metadata.origin_angle.lat.total_secs(), -30.0 * 3600.0 + 10.0 * 30.0);
+        let (lat, lon) = (window.min.lat + 0.0123, window.min.lon + 0.0234);
+        assert_eq!(window.get_elevation(lat, lon), full.get_elevation(lat, lon));
+    }
+
+    // bounds are expanded outwards to the nearest posts, and clamped
+    let bounds = options(DTEDWindow::Bounds {
+        min: AxisElement::new(-29.51, -180.0),
+        max: AxisElement::new(-29.49, -177.99),
+    });
+    let window = DTEDData::from_bytes_with_options(&raw, &bounds).unwrap();
+    assert_eq!(window.metadata.count, AxisElement::new(5, 3));
+    assert!(window.min.lat < -29.51 && window.max.lat > -29.49);
+    assert_eq!(window.min.lon, full.min.lon);
+
+    // a single post is expanded to 2 posts along each axis
+    let single = options(DTEDWindow::Indices {
+        start: AxisElement::new(120, 0),
+        end: AxisElement::new(120, 0),
+    });
+    let window = DTEDData::from_bytes_with_options(&raw, &single).unwrap();
+    assert_eq!(window.metadata.count, AxisElement::new(2, 2));
+    assert_eq!(window.max.lat, full.max.lat);
+
+    // windows outside of the data are errors
+    let outside = options(DTEDWindow::Bounds {
+        min: AxisElement::new(10.0, 10.0),
+        max: AxisElement::new(11.0, 11.0),
+    });
+    assert!(matches!(
+        DTEDData::from_bytes_with_options(&raw, &outside),
+        Err(dted2::Error::EmptyWindow)
+    ));
+
+    // only the records within the window need to be present
+    let truncated = &raw[..DT2_HEADER_LENGTH + 50 * (12 + 2 * 121)];
+    assert!(DTEDData::from_bytes_with_options(truncated, &indices).is_ok());
+    assert!(DTEDData::from_bytes(truncated).is_err());
+}
+
+#[test]
+fn test_streaming_records() {
+    use dted2::dted::{ChecksumMode, DTEDReadOptions, DTEDWindow, DT2_HEADER_LENGTH};
+    use dted2::primitives::AxisElement;
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let full = DTEDData::from_bytes(&raw).unwrap();
+
+    // every record matches the full read
+    let records = DTEDData::records(&raw[..]).unwrap();
+    assert_eq!(records.size_hint(), (0, Some(121)));
+    let mut count = 0;
+    for (record, expected) in records.zip(full.data.iter()) {
+        let record = record.unwrap();
+        assert_eq!(record.elevations, expected.elevations);
+        assert_eq!(record.checksum, expected.checksum);
+        count += 1;
+    }
+    assert_eq!(count, 121);
+
+    // windowed streaming matches the windowed read
+    let options = DTEDReadOptions {
+        window: Some(DTEDWindow::Indices {
+            start: AxisElement::new(5, 60),
+            end: AxisElement::new(15, 69),
+        }),
+        ..Default::default()
+    };
+    let window = DTEDData::from_bytes_with_options(&raw, &options).unwrap();
+    let records = DTEDData::records_with_options(&raw[..], &options).unwrap();
+    assert_eq!(records.metadata.count, window.metadata.count);
+    assert_eq!(records.metadata.origin, window.metadata.origin);
+    let records: Vec<_> = records.map(Result::unwrap).collect();
+    assert_eq!(records.len(), 10);
+    for (record, expected) in records.iter().zip(window.data.iter()) {
+        assert_eq!(record.elevations, expected.elevations);
+    }
+
+    // checksums are verified per record, and errors end iteration
+    let mut corrupt = raw.clone();
+    let record_len = 12 + 2 * 121;
+    corrupt[DT2_HEADER_LENGTH + 3 * record_len + 20] ^= 0x01;
+    let mut records = DTEDData::records(&corrupt[..]).unwrap();
+    assert!(records.by_ref().all(|record| record.is_ok()));
+    assert_eq!(records.invalid_checksums, vec![3]);
+    let options = DTEDReadOptions { checksum: ChecksumMode::Fail, ..Default::default() };
+    let records = DTEDData::records_with_options(&corrupt[..], &options).unwrap();
+    let results: Vec<_> = records.collect();
+    assert_eq!(results.len(), 4);
+    assert!(matches!(
+        results[3],
+        Err(dted2::Error::ChecksumMismatch { section: dted2::Section::Data(3), .. })
+    ));
+
+    // truncated input
+    let truncated = &raw[..DT2_HEADER_LENGTH + 2 * record_len + 7];
+    let results: Vec<_> = DTEDData::records(truncated).unwrap().collect();
+    assert_eq!(results.len(), 3);
+    assert!(matches!(
+        results[2],
+        Err(dted2::Error::Truncated { section: dted2::Section::Data(2), offset })
+            if offset == truncated.len()
+    ));
+}
+
+#[test]
+fn test_void_handling() {
+    use dted2::dted::{Elevation, DT2_HEADER_LENGTH};
+    // void the post at lat index 60, lon index 60 (-29.5, -177.5)
+    let mut raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let offset = DT2_HEADER_LENGTH + 60 * (12 + 2 * 121) + 8 + 2 * 60;
+    raw[offset] = 0xFF;
+    raw[offset + 1] = 0xFF;
+    let full = DTEDData::from_bytes(&raw).unwrap();
+    assert_eq!(full.void_count(), 1);
+    assert!(full.void_mask()[60][60]);
+    assert_eq!(full.void_mask().iter().flatten().filter(|&&void| void).count(), 1);
+
+    // the void post itself has no elevation
+    assert_eq!(full.get_elevation(-29.5, -177.5), None);
+    assert_eq!(full.get_elevation_checked(-29.5, -177.5), Some(Elevation::Void));
+    assert_eq!(full.get_elevation_checked(0.0, 0.0), None);
+
+    // interpolation near the void falls back to the neighbouring posts
+    let interval = full.metadata.interval.lat;
+    let (lat, lon) = (-29.5 + interval / 2.0, -177.5);
+    let above = full.data[60].elevations[61] as f64;
+    assert!((full.get_elevation(lat, lon).unwrap() - above).abs() < 1e-9);
+    let (lat, lon) = (-29.5 + interval / 4.0, -177.5 + interval / 4.0);
+    let elevation = full.get_elevation(lat, lon).unwrap();
+    let neighbours = [
+        full.data[60].elevations[61],
+        full.data[61].elevations[60],
+        full.data[61].elevations[61],
+    ];
+    let min = *neighbours.iter().min().unwrap() as f64;
+    let max = *neighbours.iter().max().unwrap() as f64;
+    assert!(elevation >= min && elevation <= max);
+}
+
+#[test]
+fn test_void_filling() {
+    use dted2::dted::{DTEDReadOptions, DTEDWindow, DT2_VOID};
+    use dted2::fill::FillMethod;
+    use dted2::primitives::AxisElement;
+    let original = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    // void a 3x3 block of posts, and a single post
+    let mut voided = original.clone();
+    for lon in 50..53 {
+        for lat in 70..73 {
+            voided.data[lon].elevations[lat] = DT2_VOID;
+        }
+    }
+    voided.data[0].elevations[0] = DT2_VOID;
+    assert_eq!(voided.void_count(), 10);
+
+    let methods = [
+        FillMethod::InverseDistance { radius: 3, power: 2.0 },
+        FillMethod::Laplacian { max_iterations: 1000, tolerance: 1e-3 },
+        FillMethod::Secondary(&original),
+    ];
+    for method in methods.iter() {
+        let (filled, synthesized) = voided.fill_voids(method);
+        assert_eq!(filled.void_count(), 0);
+        assert_eq!(synthesized, voided.void_mask());
+        // posts which were not void are untouched
+        for (lon, record) in filled.data.iter().enumerate() {
+            for (lat, &elev) in record.elevations.iter().enumerate() {
+                if !synthesized[lon][lat] {
+                    assert_eq!(elev, original.data[lon].elevations[lat]);
+                }
+            }
+        }
+        // filled posts lie within the range of the surrounding posts
+        let neighbours: Vec<i16> = (49..54)
+            .flat_map(|lon| (69..74).map(move |lat| (lon, lat)))
+            .filter(|&(lon, lat)| !synthesized[lon][lat])
+            .map(|(lon, lat)| original.data[lon].elevations[lat])
+            .collect();
+        let min = *neighbours.iter().min().unwrap();
+        let max = *neighbours.iter().max().unwrap();
+        let center = filled.data[51].elevations[71];
+        assert!(center >= min && center <= max);
+    }
+
+    // filling from identical data restores the original posts
+    let (filled, _) = voided.fill_voids(&FillMethod::Secondary(&original));
+    assert_eq!(filled.data, original.data);
+
+    // posts which cannot be filled remain void
+    let (filled, synthesized) =
+        voided.fill_voids(&FillMethod::InverseDistance { radius: 1, power: 2.0 });
+    assert_eq!(filled.void_count(), 1);
+    assert!(!synthesized[51][71]);
+    let options = DTEDReadOptions {
+        window: Some(DTEDWindow::Indices {
+            start: AxisElement::new(60, 0),
+            end: AxisElement::new(120, 120),
+        }),
+        ..Default::default()
+    };
+    let partial = DTEDData::read_with_options("tests/test_data_negative.dt2", &options).unwrap();
+    let (filled, synthesized) = voided.fill_voids(&FillMethod::Secondary(&partial));
+    assert_eq!(filled.void_count(), 1);
+    assert!(!synthesized[0][0]);
+}
+
+#[test]
+fn test_fill_voids_checksums() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{ChecksumMode, DTEDLevel, DTEDReadOptions, DT2_VOID};
+    use dted2::fill::FillMethod;
+    // a level 0 cell rising to the north east, with voids in two columns
+    let grid = DTEDLevel::Level0.grid(-30, -178);
+    let mut columns: Vec<Vec<i16>> = (0..grid.count.lon as i16)
+        .map(|lon| (0..grid.count.lat as i16).map(|lat| 10 * (lat + lon)).collect())
+        .collect();
+    columns[7][30] = DT2_VOID;
+    columns[8][31] = DT2_VOID;
+    let voided = DTEDBuilder::from_grid(&grid)
+        .grid(&columns, GridOrder::ColumnMajor)
+        .build()
+        .unwrap();
+    let method = FillMethod::Laplacian { max_iterations: 100, tolerance: 1e-3 };
+    let (filled, synthesized) = voided.fill_voids(&method);
+    assert!(synthesized[7][30] && synthesized[8][31]);
+    assert_eq!(filled.data[7].elevations[30], 370);
+
+    // the checksums of the filled records match their elevations
+    assert_ne!(filled.data[7].checksum, voided.data[7].checksum);
+    assert_eq!(filled.data[6].checksum, voided.data[6].checksum);
+    let mut written = Vec::new();
+    filled.write_to(&mut written).unwrap();
+    let options = DTEDReadOptions { checksum: ChecksumMode::Fail, ..Default::default() };
+    let reread = DTEDData::from_bytes_with_options(&written, &options).unwrap();
+    assert_eq!(reread.data, filled.data);
+}
+
+#[test]
+fn test_level_detection() {
+    use dted2::dted::{DTEDLevel, LevelWarning};
+    let mut raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let data = DTEDData::from_bytes(&raw).unwrap();
+    assert_eq!(data.metadata.level, Some(DTEDLevel::Level0));
+    assert!(data.metadata.level_warnings().is_empty());
+
+    // the extension agrees
+    let path = std::env::temp_dir().join("dted2_test_level.dt0");
+    std::fs::write(&path, &raw).unwrap();
+    assert!(DTEDData::read_header(&path).unwrap().level_warnings().is_empty());
+
+    // the DSI record disagrees (the version starts at byte 59 of the DSI record)
+    raw[80 + 59..80 + 64].copy_from_slice(b"DTED1");
+    let metadata = DTEDData::header_from_bytes(&raw).unwrap();
+    assert_eq!(
+        metadata.level_warnings(),
+        vec![LevelWarning::DSIMismatch { uhl: DTEDLevel::Level0, dsi: DTEDLevel::Level1 }]
+    );
+
+    // the UHL does not match any level (latitude count)
+    raw[51..55].copy_from_slice(b"0100");
+    let metadata = DTEDData::header_from_bytes(&raw[..80]).unwrap();
+    assert_eq!(metadata.level, None);
+    assert_eq!(metadata.level_warnings(), vec![LevelWarning::Unknown]);
+}
+
+#[test]
+fn test_latitude_zones() {
+    use dted2::dted::{DTEDLevel, DTEDReadOptions, DTEDWindow, LatitudeZone, LevelWarning};
+    use dted2::primitives::AxisElement;
+    // level 2 longitude counts per zone, north and south of the equator
+    for &(lat, zone, lon_count) in [
+        (10.5, LatitudeZone::I, 3601),
+        (-49.5, LatitudeZone::I, 3601),
+        (50.5, LatitudeZone::II, 1801),
+        (-69.5, LatitudeZone::II, 1801),
+        (72.0, LatitudeZone::III, 1201),
+        (-77.0, LatitudeZone::IV, 901),
+        (85.0, LatitudeZone::V, 601),
+    ]
+    .iter()
+    {
+        let grid = DTEDLevel::Level2.grid(lat, 0.5);
+        assert_eq!(grid.zone, zone);
+        assert_eq!(grid.count, AxisElement::new(3601, lon_count));
+        assert_eq!(grid.interval_secs.lon * (lon_count - 1) as f32, 3600.0);
+    }
+    assert_eq!(DTEDLevel::Level0.grid(60.0, 0.0).count, AxisElement::new(121, 61));
+    assert_eq!(DTEDLevel::Level1.grid(-80.5, 0.0).count, AxisElement::new(1201, 201));
+
+    // the bundled file is level 0, in zone I
+    let mut raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    assert!(DTEDData::header_from_bytes(&raw).unwrap().level_warnings().is_empty());
+    // windowed reads are validated against the entire cell
+    let options = DTEDReadOptions {
+        window: Some(DTEDWindow::Indices {
+            start: AxisElement::new(0, 10),
+            end: AxisElement::new(20, 30),
+        }),
+        ..Default::default()
+    };
+    let window = DTEDData::from_bytes_with_options(&raw, &options).unwrap();
+    assert!(window.metadata.level_warnings().is_empty());
+
+    // moving the origin into zone II (51S) makes the longitude spacing invalid
+    raw[12..20].copy_from_slice(b"0510000S");
+    let metadata = DTEDData::header_from_bytes(&raw[..80]).unwrap();
+    assert_eq!(
+        metadata.level_warnings(),
+        vec![LevelWarning::LonSpacingMismatch { level: DTEDLevel::Level0, zone: LatitudeZone::II }]
+    );
+}
+
+#[test]
+fn test_write_round_trip() {
+    use dted2::dted::DTEDAccuracySubRegion;
+    use dted2::primitives::{Angle, AxisElement};
+    use dted2::Error;
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let data = DTEDData::from_bytes(&raw).unwrap();
+
+    // unmodified data is written byte-for-byte
+    let mut written = Vec::new();
+    data.write_to(&mut written).unwrap();
+    assert_eq!(written, raw);
+
+    // modified elevations and header fields are written with valid checksums
+    let mut edited = data.clone();
+    edited.data[3].elevations[7] = -1234;
+    edited.metadata.accuracy = Some(25);
+    let mut written = Vec::new();
+    edited.write_to(&mut written).unwrap();
+    assert_eq!(written.len(), raw.len());
+    let reread = DTEDData::from_bytes(&written).unwrap();
+    assert!(reread.invalid_checksums.is_empty());
+    assert_eq!(reread.metadata.accuracy, Some(25));
+    assert_eq!(reread.data[3].elevations, edited.data[3].elevations);
+    // the reserved areas of the header records are preserved
+    assert_eq!(written[80..3428], raw[80..3428]);
+
+    // without the original header, the records are written from the metadata
+    let mut synthetic = data.clone();
+    synthetic.metadata.clear_header_bytes();
+    let acc = synthetic.metadata.acc.as_mut().unwrap();
+    acc.multiple_accuracy_flag = 1;
+    acc.sub_regions.push(DTEDAccuracySubRegion {
+        accuracy: acc.accuracy,
+        outline: vec![
+            AxisElement::new(Angle::new(30, 0, 0.0, true), Angle::new(178, 0, 0.0, true)),
+            AxisElement::new(Angle::new(29, 0, 0.0, true), Angle::new(178, 0, 0.0, true)),
+            AxisElement::new(Angle::new(29, 0, 0.0, true), Angle::new(177, 30, 0.0, true)),
+        ],
+    });
+    let mut written = Vec::new();
+    synthetic.write_to(&mut written).unwrap();
+    let reread = DTEDData::from_bytes(&written).unwrap();
+    assert!(reread.invalid_checksums.is_empty());
+    assert_eq!(reread.metadata.dsi, synthetic.metadata.dsi);
+    assert_eq!(reread.metadata.acc, synthetic.metadata.acc);
+    assert_eq!(reread.data, data.data);
+    // and from the UHL alone
+    synthetic.metadata.dsi = None;
+    synthetic.metadata.acc = None;
+    let mut written = Vec::new();
+    synthetic.write_to(&mut written).unwrap();
+    let reread = DTEDData::from_bytes(&written).unwrap();
+    assert!(reread.metadata.level_warnings().is_empty());
+    assert_eq!(reread.min, data.min);
+    assert_eq!(reread.max, data.max);
+    assert_eq!(reread.data, data.data);
+
+    // the records must match the counts of the metadata
+    let mut truncated = data.clone();
+    truncated.data[5].elevations.pop();
+    assert!(matches!(
+        truncated.write_to(Vec::new()),
+        Err(Error::DimensionMismatch { expected: 121, found: 120, .. })
+    ));
+}
+
+#[test]
+fn test_write_round_trip_built() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, SecurityCode, DT2_HEADER_LENGTH, DT2_VOID};
+    // a level 1 cell at 42N 15E, with negative and void posts
+    let grid = DTEDLevel::Level1.grid(42, 15);
+    let (lat_count, lon_count) = (grid.count.lat as i32, grid.count.lon as i32);
+    let rows: Vec<Vec<i16>> = (0..lat_count)
+        .map(|lat| (0..lon_count).map(|lon| (lat * 7 - lon * 3 - 400) as i16).collect())
+        .collect();
+    let mut data = DTEDBuilder::from_grid(&grid)
+        .grid(&rows, GridOrder::RowMajor)
+        .accuracy(20)
+        .security_code(SecurityCode::Unclassified)
+        .reference("BUILT")
+        .build()
+        .unwrap();
+    data.data[3].elevations[5] = DT2_VOID;
+    assert!(data.metadata.header_bytes().is_none());
+
+    // the written cell is read back, and written again byte-for-byte
+    let mut written = Vec::new();
+    data.write_to(&mut written).unwrap();
+    let reread = DTEDData::from_bytes(&written).unwrap();
+    assert!(reread.invalid_checksums.is_empty());
+    assert_eq!(reread.metadata.header_bytes(), Some(&written[..DT2_HEADER_LENGTH]));
+    assert_eq!(reread.metadata.dsi, data.metadata.dsi);
+    assert_eq!(reread.metadata.accuracy, Some(20));
+    assert_eq!(reread.metadata.reference.as_deref(), Some("BUILT"));
+    assert_eq!(reread.min, data.min);
+    assert_eq!(reread.max, data.max);
+    for (record, expected) in reread.data.iter().zip(data.data.iter()) {
+        assert_eq!(record.elevations, expected.elevations);
+    }
+    let mut rewritten = Vec::new();
+    reread.write_to(&mut rewritten).unwrap();
+    assert_eq!(rewritten, written);
+}
+
+#[test]
+fn test_write_window() {
+    use dted2::dted::{DTEDReadOptions, DTEDWindow};
+    use dted2::primitives::AxisElement;
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let full = DTEDData::from_bytes(&raw).unwrap();
+    let options = DTEDReadOptions {
+        window: Some(DTEDWindow::Indices {
+            start: AxisElement::new(10, 40),
+            end: AxisElement::new(30, 49),
+        }),
+        ..Default::default()
+    };
+    let window = DTEDData::from_bytes_with_options(&raw, &options).unwrap();
+
+    // the DSI describes the window, in agreement with the UHL
+    let mut written = Vec::new();
+    window.write_to(&mut written).unwrap();
+    let reread = DTEDData::from_bytes(&written).unwrap();
+    let dsi = reread.metadata.dsi.as_ref().unwrap();
+    assert_eq!(dsi.count, window.metadata.count);
+    assert_eq!(dsi.origin, window.metadata.origin_angle);
+    assert_eq!(dsi.sw_corner, window.metadata.origin_angle);
+    assert_eq!(dsi.ne_corner.lat.total_secs(), -30.0 * 3600.0 + 30.0 * 30.0);
+    assert_eq!(dsi.ne_corner.lon.total_secs(), -178.0 * 3600.0 + 49.0 * 30.0);
+    assert_eq!(reread.min, window.min);
+    assert_eq!(reread.max, window.max);
+    for (record, expected) in reread.data.iter().zip(window.data.iter()) {
+        assert_eq!(record.elevations, expected.elevations);
+    }
+    assert!(reread.invalid_checksums.is_empty());
+
+    // while the unmodified cell is still written byte-for-byte
+    let mut written = Vec::new();
+    full.write_to(&mut written).unwrap();
+    assert_eq!(written, raw);
+}
+
+#[test]
+fn test_builder() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, DT2_HEADER_LENGTH};
+    use dted2::Error;
+    let raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    let data = DTEDData::from_bytes(&raw).unwrap();
+    let count = data.metadata.count;
+    let builder = DTEDBuilder::new(
+        data.metadata.origin_angle,
+        data.metadata.interval_secs,
+        count,
+    );
+
+    // the same grid, in either order
+    let columns: Vec<Vec<i16>> = data.data.iter().map(|r| r.elevations.clone()).collect();
+    let rows: Vec<i16> = (0..count.lat as usize)
+        .flat_map(|lat| columns.iter().map(move |column| column[lat]))
+        .collect();
+    let from_columns = builder.clone().grid(&columns, GridOrder::ColumnMajor).build().unwrap();
+    let from_rows = builder.clone().elevations(rows.clone(), GridOrder::RowMajor).build().unwrap();
+    for built in [&from_columns, &from_rows] {
+        assert_eq!(built.data, data.data);
+        assert_eq!(built.min, data.min);
+        assert_eq!(built.max, data.max);
+        assert_eq!(built.metadata.level, Some(DTEDLevel::Level0));
+        assert!(built.metadata.level_warnings().is_empty());
+        for (lat, lon) in [(-29.5, -177.5), (-29.01, -177.99), (-29.0, -177.0)] {
+            assert_eq!(built.get_elevation(lat, lon), data.get_elevation(lat, lon));
+        }
+    }
+
+    // the written data records match the file, and the header records are valid
+    let mut written = Vec::new();
+    from_rows.write_to(&mut written).unwrap();
+    assert_eq!(written[DT2_HEADER_LENGTH..], raw[DT2_HEADER_LENGTH..]);
+    let reread = DTEDData::from_bytes(&written).unwrap();
+    assert!(reread.invalid_checksums.is_empty());
+    assert_eq!(reread.metadata.dsi, from_rows.metadata.dsi);
+    assert_eq!(reread.data, data.data);
+
+    // the elevations must match the count
+    let err = builder.clone().elevations(rows[1..].to_vec(), GridOrder::RowMajor).build();
+    assert!(matches!(err, Err(Error::DimensionMismatch { axis: "posts", .. })));
+    let err = builder.clone().grid(&columns[1..], GridOrder::ColumnMajor).build();
+    assert!(matches!(
+        err,
+        Err(Error::DimensionMismatch { axis: "longitude lines", expected: 121, found: 120 })
+    ));
+    let mut short = columns.clone();
+    short[7].pop();
+    let err = builder.grid(&short, GridOrder::ColumnMajor).build();
+    assert!(matches!(
+        err,
+        Err(Error::DimensionMismatch { axis: "latitude points", expected: 121, found: 120 })
+    ));
+}
+
+#[test]
+fn test_mosaic() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, DT2_VOID};
+    use dted2::mosaic::DTEDMosaic;
+    use dted2::primitives::AxisElement;
+    // elevations which are linear in lat/lon (integers at every level 0 post),
+    // so bilinear interpolation is exact across the seams
+    let f = |lat: f64, lon: f64| 120.0 * (lat + 30.0) + 240.0 * (lon + 178.0);
+    let tile = |lat: i16, lon: i16| {
+        let grid = DTEDLevel::Level0.grid(lat, lon);
+        let columns: Vec<Vec<i16>> = (0..121)
+            .map(|lon_i| {
+                (0..121)
+                    .map(|lat_i| {
+                        f(lat as f64 + lat_i as f64 / 120.0, lon as f64 + lon_i as f64 / 120.0)
+                            .round() as i16
+                    })
+                    .collect()
+            })
+            .collect();
+        DTEDBuilder::from_grid(&grid)
+            .grid(&columns, GridOrder::ColumnMajor)
+            .build()
+            .unwrap()
+    };
+    let mut mosaic: DTEDMosaic =
+        vec![tile(-30, -178), tile(-29, -178), tile(-30, -177)].into_iter().collect();
+    assert!(mosaic.insert(tile(-29, -177)).is_none());
+    assert!(mosaic.insert(tile(-29, -177)).is_some());
+    assert_eq!(mosaic.len(), 4);
+
+    // across the seams, and on the shared edges and corner
+    for &(lat, lon) in [
+        (-29.5, -177.5),
+        (-29.001, -177.001),
+        (-28.999, -176.999),
+        (-29.0, -177.5),
+        (-29.5, -177.0),
+        (-29.0, -177.0),
+        (-28.0, -176.0),
+        (-30.0, -178.0),
+    ]
+    .iter()
+    {
+        let elevation = mosaic.get_elevation(lat, lon).unwrap();
+        assert!((elevation - f(lat, lon)).abs() < 1e-6, "({}, {})", lat, lon);
+    }
+    assert_eq!(mosaic.tile_at(-28.5, -176.5).unwrap().min, AxisElement::new(-29.0, -177.0));
+    assert!(mosaic.get_elevation(-27.5, -177.0).is_none());
+    assert!(mosaic.get_elevation(-29.5, -175.5).is_none());
+
+    // a void post on the northern edge of a tile is taken from the tile to the north
+    let mut southern = mosaic.remove(AxisElement::new(-30, -178)).unwrap();
+    southern.data[60].elevations[120] = DT2_VOID;
+    let (lat, lon) = (-29.004, -177.5);
+    assert!((southern.get_elevation(lat, lon).unwrap() - f(lat, lon)).abs() > 1e-3);
+    mosaic.insert(southern);
+    assert!((mosaic.get_elevation(lat, lon).unwrap() - f(lat, lon)).abs() < 1e-6);
+}
+
+#[test]
+fn test_index() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, LevelWarning};
+    use dted2::index::{DTEDIndex, IndexWarning};
+    use dted2::primitives::AxisElement;
+    let root = std::env::temp_dir().join("dted2_test_index");
+    let _ = std::fs::remove_dir_all(&root);
+    for dir in ["w178", "w177", "flat"] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    let build = |level: DTEDLevel, lat: i16| {
+        let grid = level.grid(lat, -178);
+        let posts = grid.count.lat as usize * grid.count.lon as usize;
+        DTEDBuilder::from_grid(&grid)
+            .elevations(vec![100; posts], GridOrder::ColumnMajor)
+            .build()
+            .unwrap()
+    };
+    // standard layout, flat naming, and a duplicate of the same cell and level
+    std::fs::copy("tests/test_data_negative.dt2", root.join("w178/s30.dt0")).unwrap();
+    std::fs::copy("tests/test_data_negative.dt2", root.join("flat/s30_w178.dt0")).unwrap();
+    build(DTEDLevel::Level1, -30).write(root.join("w178/s30.dt1")).unwrap();
+    // a file named for the wrong cell, and a file with the wrong extension
+    std::fs::copy("tests/test_data_negative.dt2", root.join("w177/s30.dt1")).unwrap();
+    build(DTEDLevel::Level0, -29).write(root.join("w178/s29.dt2")).unwrap();
+    // files which are not DTED files, or cannot be read
+    std::fs::write(root.join("w178/readme.txt"), "not DTED").unwrap();
+    std::fs::write(root.join("w178/s31.dt2"), "not DTED").unwrap();
+
+    let index = DTEDIndex::scan(&root).unwrap();
+    assert_eq!(index.len(), 3);
+    let cell = AxisElement::new(-30, -178);
+    let levels: Vec<DTEDLevel> = index.levels(cell).map(|tile| tile.level).collect();
+    assert_eq!(levels, vec![DTEDLevel::Level0, DTEDLevel::Level1]);
+    assert_eq!(index.get(cell, DTEDLevel::Level0).unwrap().path, root.join("flat/s30_w178.dt0"));
+    assert!(index.get(cell, DTEDLevel::Level2).is_none());
+
+    // the best available level, including on the edge between cells
+    assert_eq!(index.best(-29.5, -177.5).unwrap().level, DTEDLevel::Level1);
+    assert_eq!(index.best(-28.5, -177.5).unwrap().level, DTEDLevel::Level0);
+    assert_eq!(index.best(-29.0, -177.5).unwrap().level, DTEDLevel::Level1);
+    assert!(index.best(-29.5, -176.5).is_none());
+    let data = index.best(-29.5, -177.5).unwrap().read().unwrap();
+    assert_eq!(data.get_elevation(-29.5, -177.5), Some(100.0));
+
+    // duplicates and conflicts
+    let unreadable = root.join("w178/s31.dt2");
+    assert!(index
+        .warnings
+        .iter()
+        .any(|w| matches!(w, IndexWarning::Unreadable { path, .. } if *path == unreadable)));
+    let duplicates: Vec<_> = index
+        .warnings
+        .iter()
+        .filter_map(|w| match w {
+            IndexWarning::Duplicate { level, duplicate, .. } => Some((*level, duplicate.clone())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        duplicates,
+        vec![
+            (DTEDLevel::Level0, root.join("w177/s30.dt1")),
+            (DTEDLevel::Level0, root.join("w178/s30.dt0")),
+        ]
+    );
+    assert!(index.warnings.iter().any(|w| matches!(
+        w,
+        IndexWarning::CellMismatch { named, header, .. }
+            if *named == AxisElement::new(-30, -177) && *header == cell
+    )));
+    assert!(index.warnings.iter().any(|w| matches!(
+        w,
+        IndexWarning::Level {
+            warning: LevelWarning::ExtensionMismatch { extension: DTEDLevel::Level2, .. },
+            ..
+        }
+    )));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_index_symlinks() {
+    use dted2::dted::DTEDLevel;
+    use dted2::index::DTEDIndex;
+    use dted2::primitives::AxisElement;
+    use std::os::unix::fs::symlink;
+    let root = std::env::temp_dir().join("dted2_test_index_symlinks");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("w178")).unwrap();
+    std::fs::copy("tests/test_data_negative.dt2", root.join("w178/s30.dt0")).unwrap();
+    // a loop of directories, and a link to a file
+    symlink(&root, root.join("w178/loop")).unwrap();
+    symlink(root.join("w178/s30.dt0"), root.join("s30_w178.dt0")).unwrap();
+
+    let index = DTEDIndex::scan(&root).unwrap();
+    assert_eq!(index.len(), 1);
+    let tile = index.get(AxisElement::new(-30, -178), DTEDLevel::Level0).unwrap();
+    assert_eq!(tile.path, root.join("s30_w178.dt0"));
+    assert_eq!(index.warnings.len(), 1);
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn test_cache() {
+    use dted2::cache::{CacheBudget, DTEDCache};
+    use std::sync::Arc;
+    let dir = std::env::temp_dir().join("dted2_test_cache");
+    std::fs::create_dir_all(&dir).unwrap();
+    let paths: Vec<_> = (0..3).map(|i| dir.join(format!("tile{}.dt0", i))).collect();
+    for path in paths.iter() {
+        std::fs::copy("tests/test_data_negative.dt2", path).unwrap();
+    }
+
+    // concurrent requests for the same file only read it once
+    let cache = Arc::new(DTEDCache::new(CacheBudget::Tiles(2)));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let cache = cache.clone();
+            let path = paths[0].clone();
+            std::thread::spawn(move || cache.get(path).unwrap())
+        })
+        .collect();
+    let tiles: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert!(tiles.iter().all(|tile| Arc::ptr_eq(tile, &tiles[0])));
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.tiles), (7, 1, 1));
+
+    // least recently used tiles are evicted beyond the budget
+    cache.get(&paths[1]).unwrap();
+    cache.get(&paths[0]).unwrap();
+    cache.get(&paths[2]).unwrap();
+    assert!(cache.contains(&paths[0]));
+    assert!(!cache.contains(&paths[1]));
+    assert!(cache.contains(&paths[2]));
+    let stats = cache.stats();
+    assert_eq!((stats.misses, stats.evictions, stats.tiles), (3, 1, 2));
+    // evicted tiles remain valid while held
+    assert!(tiles[0].get_elevation(-29.5, -177.5).is_some());
+    assert!(cache.remove(&paths[0]).is_some());
+    assert_eq!(cache.stats().tiles, 1);
+
+    // a byte budget of a single tile
+    let bytes = cache.stats().bytes;
+    let cache = DTEDCache::new(CacheBudget::Bytes(bytes));
+    for path in paths.iter() {
+        cache.get(path).unwrap();
+    }
+    let stats = cache.stats();
+    assert_eq!((stats.tiles, stats.bytes, stats.evictions), (1, bytes, 2));
+    assert!(cache.contains(&paths[2]));
+
+    // errors are not cached
+    assert!(cache.get(dir.join("missing.dt0")).is_err());
+    assert_eq!(cache.stats().tiles, 1);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_interpolation() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, Interpolation, DT2_VOID};
+    // elevations which are linear in lat/lon, integers at every level 0 post
+    let f = |lat: f64, lon: f64| 120.0 * (lat + 30.0) + 240.0 * (lon + 178.0);
+    let grid = DTEDLevel::Level0.grid(-30, -178);
+    let rows: Vec<Vec<i16>> = (0..121)
+        .map(|lat_i| {
+            (0..121)
+                .map(|lon_i| (lat_i + 2 * lon_i) as i16)
+                .collect()
+        })
+        .collect();
+    let mut data = DTEDBuilder::from_grid(&grid)
+        .grid(&rows, GridOrder::RowMajor)
+        .build()
+        .unwrap();
+
+    // nearest returns the raw post
+    let (lat, lon) = (-29.5 + 0.3 / 120.0, -177.5 - 0.4 / 120.0);
+    assert_eq!(data.get_elevation_with(lat, lon, Interpolation::Nearest), Some(f(-29.5, -177.5)));
+    assert_eq!(data.get_elevation_with(-29.0, -177.0, Interpolation::Nearest), Some(f(-29.0, -177.0)));
+
+    // bicubic reproduces linear surfaces within the data, and every
+    // method matches the posts exactly, including at the edges and corners
+    let interior = [(-29.5123, -177.4321), (-29.9876, -177.0123), (-29.0123, -177.9876)];
+    for &(lat, lon) in interior.iter() {
+        let bicubic = data.get_elevation_with(lat, lon, Interpolation::Bicubic).unwrap();
+        assert!((bicubic - f(lat, lon)).abs() < 1e-6);
+        let lanczos = data.get_elevation_with(lat, lon, Interpolation::Lanczos).unwrap();
+        assert!((lanczos - f(lat, lon)).abs() < 0.5);
+    }
+    let edges = [(-30.0, -178.0), (-29.0, -177.0), (-30.0, -177.5), (-29.5, -177.0)];
+    for &(lat, lon) in edges.iter() {
+        for interpolation in [Interpolation::Bicubic, Interpolation::Lanczos] {
+            let elevation = data.get_elevation_with(lat, lon, interpolation).unwrap();
+            assert!((elevation - f(lat, lon)).abs() < 1e-6, "{:?}", interpolation);
+        }
+    }
+    assert!(data.get_elevation_with(-28.9, -177.5, Interpolation::Bicubic).is_none());
+
+    // voids fall back to bilinear interpolation, and a void nearest post is void
+    data.data[60].elevations[60] = DT2_VOID;
+    let (lat, lon) = (-29.5 + 1.5 / 120.0, -177.5 + 0.5 / 120.0);
+    let bilinear = data.get_elevation_with(lat, lon, Interpolation::Bilinear);
+    assert!(bilinear.is_some());
+    assert_eq!(data.get_elevation_with(lat, lon, Interpolation::Bicubic), bilinear);
+    assert_eq!(data.get_elevation_with(lat, lon, Interpolation::Lanczos), bilinear);
+    assert_eq!(data.get_elevation_with(-29.5, -177.5, Interpolation::Nearest), None);
+    assert_eq!(data.get_elevation(lat, lon), bilinear);
+}
+
+#[test]
+fn test_batch_elevations() {
+    use dted2::dted::Interpolation;
+    use dted2::primitives::AxisElement;
+    use dted2::Error;
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    let points: Vec<AxisElement<f64>> = (0..5000)
+        .map(|i| {
+            AxisElement::new(-30.1 + (i % 97) as f64 * 0.0123, -178.1 + (i % 89) as f64 * 0.0137)
+        })
+        .collect();
+    let lats: Vec<f64> = points.iter().map(|p| p.lat).collect();
+    let lons: Vec<f64> = points.iter().map(|p| p.lon).collect();
+    for interpolation in [Interpolation::Nearest, Interpolation::Bilinear, Interpolation::Bicubic] {
+        let mut from_slices = vec![None; points.len()];
+        let mut from_points = vec![None; points.len()];
+        data.get_elevations(&lats, &lons, interpolation, &mut from_slices).unwrap();
+        data.get_elevations_at(&points, interpolation, &mut from_points).unwrap();
+        assert_eq!(from_slices, from_points);
+        for (p, elevation) in points.iter().zip(from_slices) {
+            assert_eq!(elevation, data.get_elevation_with(p.lat, p.lon, interpolation));
+        }
+    }
+    // points outside of the data
+    assert!(points.iter().any(|p| data.get_elevation(p.lat, p.lon).is_none()));
+
+    // the slices must have the same length
+    let mut out = vec![None; points.len() - 1];
+    assert!(matches!(
+        data.get_elevations(&lats, &lons[1..], Interpolation::Bilinear, &mut out),
+        Err(Error::DimensionMismatch { axis: "longitudes", .. })
+    ));
+    assert!(matches!(
+        data.get_elevations_at(&points, Interpolation::Bilinear, &mut out),
+        Err(Error::DimensionMismatch { axis: "elevations", expected: 5000, found: 4999 })
+    ));
+}
+
+#[test]
+fn test_profile() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, Elevation, DT2_VOID};
+    use dted2::mosaic::DTEDMosaic;
+    use dted2::primitives::AxisElement;
+    use dted2::terrain::{great_circle_distance, ElevationSource, ProfileSpacing, EARTH_RADIUS};
+    // elevations which are linear in lat/lon, see test_mosaic
+    let f = |lat: f64, lon: f64| 120.0 * (lat + 30.0) + 240.0 * (lon + 178.0);
+    let tile = |lat: i16, lon: i16| {
+        let grid = DTEDLevel::Level0.grid(lat, lon);
+        let columns: Vec<Vec<i16>> = (0..121)
+            .map(|lon_i| (0..121).map(|lat_i| 120 * (lat + 30) + lat_i + 2 * lon_i).collect())
+            .collect();
+        DTEDBuilder::from_grid(&grid)
+            .grid(&columns, GridOrder::ColumnMajor)
+            .build()
+            .unwrap()
+    };
+    // void posts within 10 posts of (-29.5, -177.5)
+    let mut southern = tile(-30, -178);
+    for column in &mut southern.data[50..=70] {
+        column.elevations[50..=70].fill(DT2_VOID);
+    }
+    let mosaic: DTEDMosaic = vec![southern, tile(-29, -178)].into_iter().collect();
+
+    // along a meridian, across the seam and beyond the mosaic
+    let start = AxisElement::new(-29.9, -177.5);
+    let end = AxisElement::new(-27.5, -177.5);
+    let profile = mosaic.profile(start, end, ProfileSpacing::Posts);
+    let spacing = EARTH_RADIUS * (1.0_f64 / 120.0).to_radians() * 29.9_f64.to_radians().cos();
+    assert_eq!(profile.len(), (great_circle_distance(start, end) / spacing).ceil() as usize + 1);
+    assert_eq!(profile[0].position, start);
+    assert_eq!(profile[0].distance, 0.0);
+    let last = profile.last().unwrap();
+    assert_eq!(last.position, end);
+    assert!((last.distance - great_circle_distance(start, end)).abs() < 1e-6);
+    assert!(profile.windows(2).all(|w| {
+        let step = w[1].distance - w[0].distance;
+        step > 0.0 && step <= spacing + 1e-6
+    }));
+    for sample in profile.iter() {
+        let AxisElement { lat, lon } = sample.position;
+        assert!((lon + 177.5).abs() < 1e-9);
+        if lat > -28.0 + 1e-9 {
+            assert_eq!(sample.elevation, None, "{}", lat);
+        } else if (lat + 29.5).abs() < 10.0 / 120.0 {
+            assert_eq!(sample.elevation, Some(Elevation::Void), "{}", lat);
+            assert!(sample.is_void());
+        } else if (lat + 29.5).abs() > 11.0 / 120.0 {
+            let elevation = sample.elevation.unwrap().value().unwrap();
+            assert!((elevation - f(lat, lon)).abs() < 1e-6, "{}", lat);
+        }
+    }
+    assert!(profile.iter().any(|sample| sample.elevation == Some(Elevation::Void)));
+
+    // a single sample between identical points, and just the endpoints without a spacing
+    assert_eq!(mosaic.profile(start, start, ProfileSpacing::Posts).len(), 1);
+    assert_eq!(mosaic.profile(start, end, ProfileSpacing::Meters(0.0)).len(), 2);
+    let data = mosaic.get(AxisElement::new(-29, -178)).unwrap();
+    let single = data.profile(start, end, ProfileSpacing::Meters(1000.0));
+    assert_eq!(single.len(), 268);
+    for sample in single.iter() {
+        let covered = (-29.0..=-28.0).contains(&sample.position.lat);
+        assert_eq!(sample.elevation.is_some(), covered);
+    }
+}
+
+#[test]
+fn test_line_of_sight() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, DT2_VOID};
+    use dted2::terrain::{ElevationSource, Height, LineOfSightOptions, ProfileSpacing, Viewpoint};
+    // flat terrain at sea level, with a 100 m ridge along the latitude -29.5
+    let grid = DTEDLevel::Level0.grid(-30, -178);
+    let rows: Vec<Vec<i16>> =
+        (0..121).map(|lat_i| vec![if lat_i == 60 { 100 } else { 0 }; 121]).collect();
+    let mut data = DTEDBuilder::from_grid(&grid).grid(&rows, GridOrder::RowMajor).build().unwrap();
+    let flat = LineOfSightOptions {
+        spacing: ProfileSpacing::Meters(10.0),
+        curvature: false,
+        ..Default::default()
+    };
+
+    // along a meridian, across the ridge
+    let observer = Viewpoint::new(-29.9, -177.5, Height::AboveGround(10.0));
+    let target = Viewpoint::new(-29.1, -177.5, Height::AboveGround(10.0));
+    let sight = data.line_of_sight(observer, target, &flat).unwrap();
+    assert!(!sight.visible);
+    assert_eq!((sight.observer, sight.target), (10.0, 10.0));
+    let obstruction = sight.obstruction.unwrap();
+    assert!(obstruction.position.lat < -29.5);
+    assert!(obstruction.position.lat > -29.5 - 1.0 / 120.0);
+    assert!(obstruction.distance > 0.0 && obstruction.distance < sight.distance / 2.0);
+    assert!(sight.clearance >= -90.0 - 1e-6 && sight.clearance < -88.0, "{}", sight.clearance);
+    assert_eq!(sight.voids, 0);
+
+    // above the ridge
+    let observer = Viewpoint::new(-29.9, -177.5, Height::AboveMsl(200.0));
+    let target = Viewpoint::new(-29.1, -177.5, Height::AboveGround(200.0));
+    let sight = data.line_of_sight(observer, target, &flat).unwrap();
+    assert!(sight.visible);
+    assert!(sight.obstruction.is_none());
+    assert!(sight.clearance >= 100.0 - 1e-6 && sight.clearance < 102.0, "{}", sight.clearance);
+
+    // over the flat terrain, obstructed by the curvature of the earth (~116 m
+    // at the midpoint with the standard refraction), unless the refraction
+    // bends the sight line along the earth
+    let observer = Viewpoint::new(-29.9, -177.9, Height::AboveGround(10.0));
+    let target = Viewpoint::new(-29.1, -177.9, Height::AboveGround(10.0));
+    let rows: Vec<Vec<i16>> = vec![vec![0; 121]; 121];
+    data = DTEDBuilder::from_grid(&grid).grid(&rows, GridOrder::RowMajor).build().unwrap();
+    let sight = data.line_of_sight(observer, target, &flat).unwrap();
+    assert!(sight.visible);
+    assert!((sight.clearance - 10.0).abs() < 1e-6);
+    let sight = data.line_of_sight(observer, target, &LineOfSightOptions::default()).unwrap();
+    assert!(!sight.visible);
+    assert!((sight.clearance + 106.4).abs() < 0.5, "{}", sight.clearance);
+    let options = LineOfSightOptions { refraction: 1.0, ..Default::default() };
+    assert!(data.line_of_sight(observer, target, &options).unwrap().visible);
+
+    // void terrain is skipped, but the altitude above void terrain is unknown
+    for column in &mut data.data[..] {
+        column.elevations[50..=70].fill(DT2_VOID);
+    }
+    let sight = data.line_of_sight(observer, target, &flat).unwrap();
+    assert!(sight.visible);
+    assert!(sight.voids > 0);
+    let void = Viewpoint::new(-29.5, -177.9, Height::AboveGround(10.0));
+    assert!(data.line_of_sight(observer, void, &flat).is_none());
+    let outside = Viewpoint::new(-28.5, -177.9, Height::AboveGround(10.0));
+    assert!(data.line_of_sight(observer, outside, &flat).is_none());
+    let outside = Viewpoint::new(-28.5, -177.9, Height::AboveMsl(10.0));
+    assert!(data.line_of_sight(observer, outside, &flat).unwrap().voids > 0);
+}
+
+#[test]
+fn test_viewshed() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, DT2_VOID};
+    use dted2::primitives::AxisElement;
+    use dted2::terrain::{
+        great_circle_distance, ElevationSource, Height, LineOfSightOptions, ProfileSpacing,
+        ViewshedOptions, Viewpoint,
+    };
+    let grid = DTEDLevel::Level0.grid(-30, -178);
+    let position = |lat_i: usize, lon_i: usize| {
+        AxisElement::new(-30.0 + lat_i as f64 / 120.0, -178.0 + lon_i as f64 / 120.0)
+    };
+
+    // flat terrain at sea level, only obstructed by the curvature of the earth:
+    // the horizon of 10 m with the standard refraction is ~13 km away
+    let rows: Vec<Vec<i16>> = vec![vec![0; 121]; 121];
+    let data = DTEDBuilder::from_grid(&grid).grid(&rows, GridOrder::RowMajor).build().unwrap();
+    let observer = Viewpoint::new(-29.5, -177.5, Height::AboveGround(10.0));
+    let viewshed = data.viewshed(observer, 50_000.0, &ViewshedOptions::default()).unwrap();
+    assert_eq!(viewshed.observer, 10.0);
+    for lon_i in 0..121 {
+        for lat_i in 0..121 {
+            let distance = great_circle_distance(observer.position, position(lat_i, lon_i));
+            let visible = viewshed.visible[lon_i][lat_i];
+            let horizon = viewshed.horizon[lon_i][lat_i];
+            if distance > 50_000.0 + 100.0 {
+                assert!(!visible && horizon.is_nan());
+            } else if distance < 12_000.0 {
+                assert!(visible, "({}, {})", lat_i, lon_i);
+            } else if distance > 14_000.0 {
+                assert!(!visible, "({}, {})", lat_i, lon_i);
+            }
+        }
+    }
+    let flat = ViewshedOptions { curvature: false, ..Default::default() };
+    let viewshed = data.viewshed(observer, 50_000.0, &flat).unwrap();
+    let within = (0..121)
+        .flat_map(|lon_i| (0..121).map(move |lat_i| (lat_i, lon_i)))
+        .filter(|&(lat_i, lon_i)| {
+            great_circle_distance(observer.position, position(lat_i, lon_i)) < 49_900.0
+        })
+        .count();
+    assert!(viewshed.visible_count() >= within);
+    assert!(viewshed.visible_count() <= within + 121 * 2);
+
+    // a 100 m ridge along the latitude -29.4, casting a shadow to the north
+    let rows: Vec<Vec<i16>> =
+        (0..121).map(|lat_i| vec![if lat_i == 72 { 100 } else { 0 }; 121]).collect();
+    let mut data = DTEDBuilder::from_grid(&grid).grid(&rows, GridOrder::RowMajor).build().unwrap();
+    let viewshed = data.viewshed(observer, 1e6, &flat).unwrap();
+    assert!(viewshed.visible[60][72]);
+    assert!(!viewshed.visible[60][73]);
+    assert!(!viewshed.visible[60][120]);
+    assert!(viewshed.visible[60][0]);
+    assert!(viewshed.visible[0][60] && viewshed.visible[120][60]);
+    // the horizon behind the ridge is the angle of its top
+    let horizon = viewshed.horizon[60][100] as f64;
+    let ridge = great_circle_distance(observer.position, position(72, 60));
+    assert!((horizon - (90.0 / ridge).atan().to_degrees()).abs() < 0.01);
+    assert_eq!(viewshed.horizon[60][60], -90.0);
+
+    // agrees with the line of sight to (almost) every post
+    let options = LineOfSightOptions {
+        spacing: ProfileSpacing::Meters(50.0),
+        curvature: false,
+        ..Default::default()
+    };
+    let mut disagree = 0;
+    for lon_i in (0..121).step_by(4) {
+        for lat_i in (0..121).step_by(4) {
+            let target = position(lat_i, lon_i);
+            let target = Viewpoint::new(target.lat, target.lon, Height::AboveGround(0.0));
+            let sight = data.line_of_sight(observer, target, &options).unwrap();
+            if sight.visible != viewshed.visible[lon_i][lat_i] {
+                disagree += 1;
+            }
+        }
+    }
+    assert!(disagree <= 31 * 31 / 50, "{}", disagree);
+
+    // void posts are not visible, and do not obstruct
+    for column in &mut data.data[..] {
+        column.elevations[71..=73].fill(DT2_VOID);
+    }
+    let viewshed = data.viewshed(observer, 1e6, &flat).unwrap();
+    assert!(!viewshed.visible[60][72]);
+    assert_eq!(viewshed.visible_count(), 121 * 121 - 3 * 121);
+    // the observer must be within the tile, above known terrain
+    let outside = Viewpoint::new(-28.5, -177.5, Height::AboveMsl(10.0));
+    assert!(data.viewshed(outside, 1e6, &flat).is_none());
+    let void = Viewpoint::new(-29.4, -177.5, Height::AboveGround(10.0));
+    assert!(data.viewshed(void, 1e6, &flat).is_none());
+}
+
+#[test]
+fn test_slope_aspect() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::{DTEDLevel, DT2_VOID};
+    use dted2::terrain::{SlopeKernel, SlopeUnit, EARTH_RADIUS};
+    let grid = DTEDLevel::Level0.grid(-30, -178);
+    let meters_per_degree = EARTH_RADIUS * 1.0_f64.to_radians();
+    let lat_meters = meters_per_degree / 120.0;
+    let lon_meters = |lat: f64| meters_per_degree / 120.0 * lat.to_radians().cos();
+    let kernels = [SlopeKernel::Horn, SlopeKernel::ZevenbergenThorne];
+
+    // rising 50 m per post to the north, facing south (including the edges)
+    let rows: Vec<Vec<i16>> = (0..121).map(|lat_i| vec![50 * lat_i; 121]).collect();
+    let data = DTEDBuilder::from_grid(&grid).grid(&rows, GridOrder::RowMajor).build().unwrap();
+    let expected = (50.0 / lat_meters).atan().to_degrees();
+    for kernel in kernels {
+        let (slope, aspect) = data.slope_aspect(kernel, SlopeUnit::Degrees);
+        for (lon_i, lat_i) in [(60, 60), (0, 0), (120, 120), (0, 60), (60, 120)] {
+            assert!((slope[lon_i][lat_i] as f64 - expected).abs() < 1e-4);
+            assert!((aspect[lon_i][lat_i] - 180.0).abs() < 1e-4);
+        }
+        let (percent, _) = data.slope_aspect(kernel, SlopeUnit::Percent);
+        assert!((percent[60][60] as f64 - 5000.0 / lat_meters).abs() < 1e-3);
+    }
+    let slope = data.get_slope(-29.45, -177.55).unwrap();
+    assert!((slope - expected).abs() < 1e-9);
+    assert!((data.get_aspect(-29.45, -177.55).unwrap() - 180.0).abs() < 1e-9);
+
+    // falling 30 m per post to the east, facing east, where the longitude
+    // lines converge (and the slope steepens) towards the south pole
+    let rows: Vec<Vec<i16>> =
+        (0..121).map(|_| (0..121).map(|lon_i| -30 * lon_i).collect()).collect();
+    let data = DTEDBuilder::from_grid(&grid).grid(&rows, GridOrder::RowMajor).build().unwrap();
+    for kernel in kernels {
+        let (slope, aspect) = data.slope_aspect(kernel, SlopeUnit::Degrees);
+        for lat_i in [0, 60, 120] {
+            let expected = (30.0 / lon_meters(-30.0 + lat_i as f64 / 120.0)).atan().to_degrees();
+            assert!((slope[60][lat_i] as f64 - expected).abs() < 1e-4);
+            assert!((aspect[60][lat_i] - 90.0).abs() < 1e-4);
+        }
+        assert!(slope[60][0] > slope[60][120]);
+    }
+    let gradient = data.get_gradient(-29.5, -177.5, SlopeKernel::Horn).unwrap();
+    assert!((gradient.east + 30.0 / lon_meters(-29.5)).abs() < 1e-9);
+    assert!(gradient.north.abs() < 1e-9);
+    assert!((data.get_aspect(-29.5, -177.5).unwrap() - 90.0).abs() < 1e-9);
+
+    // flat terrain has no aspect
+    let rows: Vec<Vec<i16>> = vec![vec![7; 121]; 121];
+    let mut data = DTEDBuilder::from_grid(&grid).grid(&rows, GridOrder::RowMajor).build().unwrap();
+    let (slope, aspect) = data.slope_aspect(SlopeKernel::Horn, SlopeUnit::Degrees);
+    assert_eq!(slope[60][60], 0.0);
+    assert!(aspect[60][60].is_nan());
+    assert_eq!(data.get_slope(-29.5, -177.5), Some(0.0));
+    assert_eq!(data.get_aspect(-29.5, -177.5), None);
+
+    // void posts are excluded from the kernels of their neighbours
+    data.data[60].elevations[60] = DT2_VOID;
+    let (horn, _) = data.slope_aspect(SlopeKernel::Horn, SlopeUnit::Degrees);
+    let (zt, _) = data.slope_aspect(SlopeKernel::ZevenbergenThorne, SlopeUnit::Degrees);
+    assert!(horn[60][60].is_nan() && zt[60][60].is_nan());
+    assert!(horn[61][61].is_nan() && zt[61][61] == 0.0);
+    assert!(horn[61][60].is_nan() && zt[61][60].is_nan());
+    assert_eq!(horn.iter().flatten().filter(|slope| slope.is_nan()).count(), 9);
+    assert_eq!(zt.iter().flatten().filter(|slope| slope.is_nan()).count(), 5);
+    assert!(data.get_slope(-29.5, -177.5).is_none());
+    assert_eq!(data.get_slope(-29.5, -177.5 + 1.5 / 120.0), Some(0.0));
+    assert!(data.get_slope(-28.5, -177.5).is_none());
+}
+
+#[test]
+fn test_builder_minimum_count() {
+    use dted2::builder::{DTEDBuilder, GridOrder};
+    use dted2::dted::DTEDLevel;
+    use dted2::primitives::AxisElement;
+    use dted2::Error;
+    // interpolation requires at least 2 posts along each axis
+    let builder = |lat: u16, lon: u16| {
+        let mut grid = DTEDLevel::Level0.grid(-30, -178);
+        grid.count = AxisElement::new(lat, lon);
+        DTEDBuilder::from_grid(&grid)
+            .elevations(vec![0; lat as usize * lon as usize], GridOrder::RowMajor)
+            .build()
+    };
+    for (lat, lon) in [(1, 121), (0, 121)] {
+        assert!(matches!(
+            builder(lat, lon),
+            Err(Error::DimensionMismatch { axis: "latitude points", expected: 2, .. })
+        ));
+    }
+    for (lat, lon) in [(121, 1), (121, 0)] {
+        assert!(matches!(
+            builder(lat, lon),
+            Err(Error::DimensionMismatch { axis: "longitude lines", expected: 2, .. })
+        ));
+    }
+    let data = builder(2, 2).unwrap();
+    assert_eq!(data.get_elevation(-30.0, -178.0), Some(0.0));
+}
+
+#[test]
+fn test_level_warnings_on_read() {
+    use dted2::dted::{DTEDLevel, DTEDReadOptions, DTEDWindow, LatitudeZone, LevelWarning};
+    use dted2::primitives::AxisElement;
+    // a level 0 file, named as level 2, whose DSI record claims level 1
+    let mut raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    raw[80 + 59..80 + 64].copy_from_slice(b"DTED1");
+    let path = std::env::temp_dir().join("dted2_test_level_warnings.dt2");
+    std::fs::write(&path, &raw).unwrap();
+    let expected = vec![
+        LevelWarning::DSIMismatch { uhl: DTEDLevel::Level0, dsi: DTEDLevel::Level1 },
+        LevelWarning::ExtensionMismatch { uhl: DTEDLevel::Level0, extension: DTEDLevel::Level2 },
+    ];
+
+    // every read path surfaces the warnings, without a filename where there is none
+    assert_eq!(DTEDData::read(&path).unwrap().metadata.warnings, expected);
+    assert_eq!(DTEDData::read_header(&path).unwrap().warnings, expected);
+    let options = DTEDReadOptions {
+        window: Some(DTEDWindow::Indices {
+            start: AxisElement::new(0, 10),
+            end: AxisElement::new(20, 30),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(DTEDData::read_with_options(&path, &options).unwrap().metadata.warnings, expected);
+    assert_eq!(DTEDData::from_bytes(&raw).unwrap().metadata.warnings, expected[..1]);
+    let reader = DTEDData::from_reader(raw.as_slice()).unwrap();
+    assert_eq!(reader.metadata.warnings, expected[..1]);
+    let records = DTEDData::records(raw.as_slice()).unwrap();
+    assert_eq!(records.metadata.warnings, expected[..1]);
+    #[cfg(feature = "mmap")]
+    assert_eq!(dted2::mmap::DTEDMmap::open(&path).unwrap().metadata.warnings, expected);
+
+    // the UHL alone is validated against the latitude zone (51S)
+    raw[12..20].copy_from_slice(b"0510000S");
+    let metadata = DTEDData::header_from_bytes(&raw[..80]).unwrap();
+    assert_eq!(
+        metadata.warnings,
+        vec![LevelWarning::LonSpacingMismatch { level: DTEDLevel::Level0, zone: LatitudeZone::II }]
+    );
+    // and the warnings of valid files are empty
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    assert_eq!(data.metadata.warnings, data.metadata.level_warnings());
+    assert!(DTEDData::from_bytes(&std::fs::read("tests/test_data_negative.dt2").unwrap())
+        .unwrap()
+        .metadata
+        .warnings
+        .is_empty());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap_matches_read() {
+    use dted2::mmap::DTEDMmap;
+    let data = DTEDData::read("tests/test_data_negative.dt2").unwrap();
+    let mapped = DTEDMmap::open("tests/test_data_negative.dt2").unwrap();
+    assert_eq!(mapped.min, data.min);
+    assert_eq!(mapped.max, data.max);
+    assert!(mapped.invalid_checksums.is_empty());
+    for (lon_idx, record) in data.data.iter().enumerate() {
+        for (lat_idx, &elevation) in record.elevations.iter().enumerate() {
+            assert_eq!(mapped.get_post(lat_idx, lon_idx), Some(elevation));
+        }
+    }
+    for (lat, lon) in [(-29.5, -177.5), (-29.01, -177.99), (-29.0, -177.0), (-30.0, -178.0)] {
+        assert_eq!(mapped.get_elevation(lat, lon), data.get_elevation(lat, lon));
+        assert_eq!(mapped.get_indices(lat, lon), data.get_indices(lat, lon));
+    }
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_mmap_checksums() {
+    use dted2::dted::{ChecksumMode, DTEDReadOptions, DT2_HEADER_LENGTH};
+    use dted2::mmap::DTEDMmap;
+    // corrupt an elevation in column 5
+    let mut raw = std::fs::read("tests/test_data_negative.dt2").unwrap();
+    raw[DT2_HEADER_LENGTH + 5 * (12 + 2 * 121) + 20] ^= 0x01;
+    let path = std::env::temp_dir().join("dted2_test_mmap_checksum.dt2");
+    std::fs::write(&path, &raw).unwrap();
+
+    // not verified by default, so the data records are not read on open
+    assert!(DTEDMmap::open(&path).unwrap().invalid_checksums.is_empty());
+    let options = DTEDReadOptions { checksum: ChecksumMode::Warn, ..Default::default() };
+    assert_eq!(DTEDMmap::open_with_options(&path, &options).unwrap().invalid_checksums, vec![5]);
+}